@@ -0,0 +1,232 @@
+use alloc::{collections::BTreeSet, sync::Arc};
+use core::{fmt, ptr, slice};
+
+use bytes::Bytes;
+
+use crate::{vec::RawChunk, BytesRef, FastStr, INLINE_CAP};
+
+/// An arena that packs many strings into a few large shared allocations.
+///
+/// Every string that is too long to be inlined is copied into the current chunk and returned as
+/// a view of the one `Bytes` that owns the chunk, so all the strings of a chunk share it. The
+/// chunk is freed once the arena has moved on and every string written to it has been dropped.
+///
+/// Strings that can be inlined never touch the arena, and strings larger than the chunk size get
+/// an allocation of their own.
+pub struct FastStrArena {
+    chunk_size: usize,
+    /// The current chunk, if any. Bytes are only ever written past `used`.
+    current: Option<Chunk>,
+    interned: BTreeSet<FastStr>,
+    usage: ArenaUsage,
+}
+
+struct Chunk {
+    raw: Arc<RawChunk>,
+    /// A view over the whole chunk, which every string written to it is sliced from. It is never
+    /// read, since the tail of the chunk is still being written.
+    whole: BytesRef,
+    used: usize,
+}
+
+impl Chunk {
+    fn new(size: usize) -> Self {
+        let raw = Arc::new(RawChunk::new(size));
+        let owner = Bytes::from_owner(Owner(raw.clone()));
+        let whole = BytesRef {
+            ptr: raw.ptr.as_ptr(),
+            len: size,
+            data: Arc::new(owner),
+        };
+        Self {
+            raw,
+            whole,
+            used: 0,
+        }
+    }
+
+    #[inline]
+    fn remaining(&self) -> usize {
+        self.whole.len - self.used
+    }
+}
+
+/// The owner of the `Bytes` shared by the strings of a chunk.
+///
+/// Its `Bytes` is empty, so that no reference ever covers the bytes that are still being
+/// written. The strings are views of the chunk, each created once its bytes have been written.
+struct Owner(Arc<RawChunk>);
+
+impl AsRef<[u8]> for Owner {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        // Safety: an empty slice at the start of the chunk.
+        unsafe { slice::from_raw_parts(self.0.ptr.as_ptr(), 0) }
+    }
+}
+
+/// Memory usage of a [`FastStrArena`], as reported by [`FastStrArena::memory_usage`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ArenaUsage {
+    /// Number of chunks allocated so far.
+    pub chunks: usize,
+    /// Number of strings larger than the chunk size, which got an allocation of their own.
+    pub oversize: usize,
+    /// Total bytes allocated for chunks and oversize strings.
+    pub allocated: usize,
+    /// Bytes of string data written into chunks and oversize strings.
+    pub used: usize,
+}
+
+impl FastStrArena {
+    /// The chunk size used by [`FastStrArena::new`].
+    pub const DEFAULT_CHUNK_SIZE: usize = 64 * 1024;
+
+    /// Create a new arena with the default chunk size.
+    #[inline]
+    pub fn new() -> Self {
+        Self::with_chunk_size(Self::DEFAULT_CHUNK_SIZE)
+    }
+
+    /// Create a new arena that allocates chunks of `chunk_size` bytes.
+    ///
+    /// This constructor panics if `chunk_size` is not larger than the inline capacity.
+    pub fn with_chunk_size(chunk_size: usize) -> Self {
+        assert!(
+            chunk_size > INLINE_CAP,
+            "[FastStr] arena chunk size must be larger than {INLINE_CAP}",
+        );
+        Self {
+            chunk_size,
+            current: None,
+            interned: BTreeSet::new(),
+            usage: ArenaUsage::default(),
+        }
+    }
+
+    /// Copy `s` into the arena and return a `FastStr` that shares the chunk it was written to.
+    pub fn alloc(&mut self, s: &str) -> FastStr {
+        if s.len() <= INLINE_CAP {
+            return FastStr::new(s);
+        }
+        if s.len() > self.chunk_size {
+            self.usage.oversize += 1;
+            self.usage.allocated += s.len();
+            self.usage.used += s.len();
+            return FastStr::new(s);
+        }
+        let chunk = match &mut self.current {
+            Some(chunk) if chunk.remaining() >= s.len() => chunk,
+            current => {
+                self.usage.chunks += 1;
+                self.usage.allocated += self.chunk_size;
+                current.insert(Chunk::new(self.chunk_size))
+            }
+        };
+        self.usage.used += s.len();
+        // Safety: the destination is inside the unwritten tail of the chunk, which no view
+        // covers, and the written bytes are never modified again.
+        unsafe {
+            let dst = chunk.raw.ptr.as_ptr().add(chunk.used);
+            ptr::copy_nonoverlapping(s.as_ptr(), dst, s.len());
+            chunk.used += s.len();
+            let written = chunk.whole.slice_ref(slice::from_raw_parts(dst, s.len()));
+            FastStr::from_bytes_ref_unchecked(written)
+        }
+    }
+
+    /// Like [`FastStrArena::alloc`], but returns the previously allocated `FastStr` if an equal
+    /// string has already been deduplicated by this arena.
+    pub fn dedup(&mut self, s: &str) -> FastStr {
+        if let Some(existing) = self.interned.get(s) {
+            return existing.clone();
+        }
+        let new = self.alloc(s);
+        if !FastStr::can_inline(&new) {
+            self.interned.insert(new.clone());
+        }
+        new
+    }
+
+    /// Return the memory usage of the arena.
+    #[inline]
+    pub fn memory_usage(&self) -> ArenaUsage {
+        self.usage
+    }
+}
+
+impl Default for FastStrArena {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for FastStrArena {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("FastStrArena")
+            .field("chunk_size", &self.chunk_size)
+            .field("usage", &self.usage)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LONG: &str = "a string that is too long to be inlined";
+
+    #[test]
+    fn test_alloc_shares_chunk() {
+        let mut arena = FastStrArena::with_chunk_size(100);
+        let a = arena.alloc(LONG);
+        let b = arena.alloc(LONG);
+        let short = arena.alloc("short");
+        assert_eq!(a, LONG);
+        assert_eq!(b, LONG);
+        assert_eq!(short, "short");
+        assert!(a.shares_allocation_with(&b));
+        assert_eq!(a.ref_count(), Some(3));
+
+        let c = arena.alloc(LONG);
+        assert!(!a.shares_allocation_with(&c));
+        assert_eq!(c.ref_count(), Some(2));
+        let usage = arena.memory_usage();
+        assert_eq!(usage.chunks, 2);
+        assert_eq!(usage.allocated, 200);
+        assert_eq!(usage.used, LONG.len() * 3);
+
+        drop(arena);
+        drop(b);
+        assert_eq!(a.ref_count(), Some(1));
+        assert_eq!(a.into_bytes(), LONG.as_bytes());
+        assert_eq!(c, LONG);
+    }
+
+    #[test]
+    fn test_dedup() {
+        let mut arena = FastStrArena::new();
+        let a = arena.dedup(LONG);
+        let b = arena.dedup(LONG);
+        assert_eq!(a.as_ptr(), b.as_ptr());
+        assert_eq!(arena.memory_usage().used, LONG.len());
+    }
+
+    #[test]
+    fn test_oversize_usage() {
+        let mut arena = FastStrArena::with_chunk_size(64);
+        assert_eq!(arena.alloc(&"x".repeat(100)).len(), 100);
+        assert_eq!(
+            arena.memory_usage(),
+            ArenaUsage {
+                chunks: 0,
+                oversize: 1,
+                allocated: 100,
+                used: 100,
+            }
+        );
+    }
+
+    static_assertions::assert_impl_all!(FastStrArena: Send, Sync);
+}
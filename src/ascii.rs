@@ -0,0 +1,235 @@
+use alloc::vec::Vec;
+use core::{
+    fmt,
+    ops::{Bound, RangeBounds},
+};
+
+use crate::{FastStr, INLINE_CAP};
+
+/// A `FastStr` that only contains ASCII text: printable ASCII characters and horizontal tabs,
+/// but no other control character.
+///
+/// This is what protocol fields such as HTTP header values, method names and base64 payloads
+/// are made of, so an `AsciiFastStr` converts into an `http::HeaderValue` without a check.
+///
+/// Since every character is a single byte, byte offsets are character offsets: indexing is
+/// O(1) and never falls in the middle of a character, and the case transforms are plain byte
+/// operations.
+///
+/// Create one with [`TryFrom<FastStr>`], which checks the string once, and turn it back into a
+/// `FastStr` for free with [`From`].
+#[derive(Clone, Default)]
+pub struct AsciiFastStr(FastStr);
+
+impl AsciiFastStr {
+    /// Create a new `AsciiFastStr` from a static string slice.
+    ///
+    /// This panics if `s` is not ASCII text, which fails the build in a `const` context.
+    #[inline]
+    pub const fn from_static_str(s: &'static str) -> Self {
+        let bytes = s.as_bytes();
+        let mut i = 0;
+        while i < bytes.len() {
+            assert!(is_text(bytes[i]), "string is not ASCII text");
+            i += 1;
+        }
+        Self(FastStr::from_static_str(s))
+    }
+
+    /// Create a new `AsciiFastStr` without checking that `s` is ASCII text.
+    ///
+    /// # Safety
+    ///
+    /// `s` must only contain printable ASCII characters and horizontal tabs.
+    #[inline]
+    pub unsafe fn new_unchecked(s: FastStr) -> Self {
+        Self(s)
+    }
+
+    /// Return the character at index `i`, or `None` if `i` is out of bounds.
+    #[inline]
+    pub fn char_at(&self, i: usize) -> Option<char> {
+        self.as_bytes().get(i).map(|&b| b as char)
+    }
+
+    /// Return the substring covering `range`, sharing the buffer when possible.
+    ///
+    /// This panics if `range` is out of bounds.
+    #[inline]
+    pub fn slice<R: RangeBounds<usize>>(&self, range: R) -> Self {
+        let start = match range.start_bound() {
+            Bound::Included(&i) => i,
+            Bound::Excluded(&i) => i + 1,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&i) => i + 1,
+            Bound::Excluded(&i) => i,
+            Bound::Unbounded => self.len(),
+        };
+        Self(self.0.slice_ref(&self.as_str()[start..end]))
+    }
+
+    /// Return the string with every letter converted to uppercase.
+    ///
+    /// The string is cloned without copying if it has no lowercase letter.
+    #[inline]
+    pub fn to_ascii_uppercase(&self) -> Self {
+        if !self.bytes().any(|b| b.is_ascii_lowercase()) {
+            return self.clone();
+        }
+        self.map_bytes(<[u8]>::make_ascii_uppercase)
+    }
+
+    /// Return the string with every letter converted to lowercase.
+    ///
+    /// The string is cloned without copying if it has no uppercase letter.
+    #[inline]
+    pub fn to_ascii_lowercase(&self) -> Self {
+        if !self.bytes().any(|b| b.is_ascii_uppercase()) {
+            return self.clone();
+        }
+        self.map_bytes(<[u8]>::make_ascii_lowercase)
+    }
+
+    fn map_bytes(&self, f: fn(&mut [u8])) -> Self {
+        let len = self.len();
+        let s = if len <= INLINE_CAP {
+            let mut buf = [0; INLINE_CAP];
+            buf[..len].copy_from_slice(self.as_bytes());
+            f(&mut buf[..len]);
+            // Safety: the case transforms map ASCII text to ASCII text.
+            FastStr::new(unsafe { core::str::from_utf8_unchecked(&buf[..len]) })
+        } else {
+            let mut v = Vec::from(self.as_bytes());
+            f(&mut v);
+            // Safety: the case transforms map ASCII text to ASCII text.
+            unsafe { FastStr::from_vec_u8_unchecked(v) }
+        };
+        Self(s)
+    }
+}
+
+/// The error returned when converting a string that is not ASCII text into an [`AsciiFastStr`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NotAsciiError {
+    inner: FastStr,
+    valid_up_to: usize,
+}
+
+impl NotAsciiError {
+    /// Return the length of the ASCII text prefix of the string.
+    #[inline]
+    pub fn valid_up_to(&self) -> usize {
+        self.valid_up_to
+    }
+
+    /// Return the string that failed the conversion.
+    #[inline]
+    pub fn into_inner(self) -> FastStr {
+        self.inner
+    }
+}
+
+impl fmt::Display for NotAsciiError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "non-ASCII or control character at byte offset {}",
+            self.valid_up_to
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for NotAsciiError {}
+
+impl TryFrom<FastStr> for AsciiFastStr {
+    type Error = NotAsciiError;
+
+    #[inline]
+    fn try_from(s: FastStr) -> Result<Self, Self::Error> {
+        // `str::is_ascii` checks a word at a time, so the byte-by-byte scan for control characters
+        // only runs on ASCII strings.
+        if s.is_ascii() && s.bytes().all(is_text) {
+            return Ok(Self(s));
+        }
+        let valid_up_to = s.bytes().position(|b| !is_text(b)).unwrap_or(s.len());
+        Err(NotAsciiError {
+            inner: s,
+            valid_up_to,
+        })
+    }
+}
+
+impl TryFrom<&'static str> for AsciiFastStr {
+    type Error = NotAsciiError;
+
+    #[inline]
+    fn try_from(s: &'static str) -> Result<Self, Self::Error> {
+        Self::try_from(FastStr::from_static_str(s))
+    }
+}
+
+impl_faststr_newtype!([] AsciiFastStr, 0);
+
+/// Return `true` if `b` is a printable ASCII character or a horizontal tab, the bytes allowed in
+/// an HTTP header value.
+#[inline]
+const fn is_text(b: u8) -> bool {
+    matches!(b, b'\t' | b' '..=b'~')
+}
+
+impl AsRef<[u8]> for AsciiFastStr {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+/// This conversion is infallible because an `AsciiFastStr` only holds bytes that are valid in a
+/// header value, and it never copies a string backed by [`Bytes`](bytes::Bytes).
+#[cfg(feature = "http")]
+impl From<AsciiFastStr> for http::HeaderValue {
+    #[inline]
+    fn from(s: AsciiFastStr) -> Self {
+        // Safety: ASCII text only contains bytes that are valid in a header value.
+        unsafe { http::HeaderValue::from_maybe_shared_unchecked(s.0.into_bytes()) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ascii() {
+        const METHOD: AsciiFastStr = AsciiFastStr::from_static_str("GetUserInfo");
+        assert_eq!(METHOD.char_at(3), Some('U'));
+        assert_eq!(METHOD.char_at(11), None);
+        assert_eq!(METHOD.slice(3..7), "User");
+        assert_eq!(METHOD.to_ascii_lowercase(), "getuserinfo");
+        assert_eq!(METHOD.to_ascii_uppercase(), "GETUSERINFO");
+        assert!(METHOD.to_ascii_uppercase().as_faststr().is_inline());
+        assert!(METHOD.into_inner().is_static());
+
+        let err = AsciiFastStr::try_from(FastStr::new("naïve")).unwrap_err();
+        assert_eq!(err.valid_up_to(), 2);
+        assert_eq!(err.into_inner(), "naïve");
+        let err = AsciiFastStr::try_from(FastStr::new("line\r\nbreak")).unwrap_err();
+        assert_eq!(err.valid_up_to(), 4);
+        assert!(AsciiFastStr::try_from("tab\tseparated ~text~").is_ok());
+    }
+
+    #[cfg(feature = "http")]
+    #[test]
+    fn test_header_value() {
+        let s = AsciiFastStr::try_from(FastStr::from_string(
+            "Bearer a-token-too-long-to-be-inlined".into(),
+        ))
+        .unwrap();
+        let value = http::HeaderValue::from(s.clone());
+        assert_eq!(value.as_bytes(), s.as_bytes());
+        assert_eq!(value.as_bytes().as_ptr(), s.as_ptr());
+    }
+}
@@ -0,0 +1,112 @@
+use std::{fmt, sync::Arc};
+
+use arc_swap::ArcSwap;
+
+use crate::FastStr;
+
+/// A `FastStr` that can be read and replaced atomically from many threads.
+///
+/// Reads are lock-free and cost about as much as a `FastStr` clone, which makes this a good
+/// fit for configuration values that are read on every request and updated rarely.
+///
+/// ```
+/// use faststr::{AtomicFastStr, FastStr};
+///
+/// let upstream = AtomicFastStr::new(FastStr::from_static_str("10.0.0.1:8080"));
+/// upstream.store(FastStr::from_static_str("10.0.0.2:8080"));
+/// assert_eq!(upstream.load(), "10.0.0.2:8080");
+/// ```
+pub struct AtomicFastStr {
+    inner: ArcSwap<FastStr>,
+}
+
+impl AtomicFastStr {
+    /// Create a new `AtomicFastStr` holding `s`.
+    #[inline]
+    pub fn new(s: FastStr) -> Self {
+        Self {
+            inner: ArcSwap::from_pointee(s),
+        }
+    }
+
+    /// Return the current value.
+    #[inline]
+    pub fn load(&self) -> FastStr {
+        FastStr::clone(&self.inner.load())
+    }
+
+    /// Replace the current value with `s`.
+    #[inline]
+    pub fn store(&self, s: FastStr) {
+        self.inner.store(Arc::new(s))
+    }
+
+    /// Replace the current value with `s`, returning the previous value.
+    #[inline]
+    pub fn swap(&self, s: FastStr) -> FastStr {
+        Arc::unwrap_or_clone(self.inner.swap(Arc::new(s)))
+    }
+
+    /// Replace the current value with `new` if it is equal to `current`.
+    ///
+    /// Returns the previous value, so the swap happened if and only if the returned value is
+    /// equal to `current`. Unlike [`ArcSwap::compare_and_swap`], the values are compared by
+    /// content, not by address.
+    pub fn compare_and_swap(&self, current: &str, new: FastStr) -> FastStr {
+        let new = Arc::new(new);
+        loop {
+            let cur = self.inner.load();
+            if cur.as_str() != current {
+                return FastStr::clone(&cur);
+            }
+            let prev = self.inner.compare_and_swap(&cur, new.clone());
+            if Arc::ptr_eq(&prev, &cur) {
+                return FastStr::clone(&prev);
+            }
+        }
+    }
+
+    /// Consume the `AtomicFastStr` and return the current value.
+    #[inline]
+    pub fn into_inner(self) -> FastStr {
+        Arc::unwrap_or_clone(self.inner.into_inner())
+    }
+}
+
+impl Default for AtomicFastStr {
+    #[inline]
+    fn default() -> Self {
+        Self::new(FastStr::empty())
+    }
+}
+
+impl From<FastStr> for AtomicFastStr {
+    #[inline]
+    fn from(s: FastStr) -> Self {
+        Self::new(s)
+    }
+}
+
+impl fmt::Debug for AtomicFastStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.load(), f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_atomic() {
+        let s = AtomicFastStr::new(FastStr::from_static_str("a"));
+        assert_eq!(s.swap(FastStr::from_static_str("b")), "a");
+        assert_eq!(s.compare_and_swap("a", FastStr::from_static_str("c")), "b");
+        assert_eq!(s.load(), "b");
+        assert_eq!(
+            s.compare_and_swap("b", FastStr::from_string("c".repeat(40))),
+            "b"
+        );
+        assert_eq!(s.into_inner(), "c".repeat(40));
+    }
+}
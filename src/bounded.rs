@@ -0,0 +1,154 @@
+use alloc::string::String;
+use core::fmt;
+
+use bytes::Bytes;
+use simdutf8::basic::{from_utf8, Utf8Error};
+
+use crate::FastStr;
+
+/// A `FastStr` of at most `MAX` bytes.
+///
+/// The length is checked by every constructor, before anything is copied, so that oversize
+/// strings are rejected at the edge instead of by the database or the peer.
+///
+/// sqlx type infos cannot carry a length, so the `sqlx-*` features report a plain `VARCHAR`
+/// column and check the bound when decoding.
+#[derive(Clone, Default)]
+pub struct BoundedFastStr<const MAX: usize>(pub(crate) FastStr);
+
+/// The error returned when a [`BoundedFastStr`] cannot be created.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum BoundedError {
+    /// The string is longer than the bound.
+    TooLong {
+        /// The length of the string in bytes.
+        len: usize,
+        /// The maximum length in bytes.
+        max: usize,
+    },
+    /// The bytes are not valid UTF-8.
+    Utf8(Utf8Error),
+}
+
+impl fmt::Display for BoundedError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::TooLong { len, max } => {
+                write!(f, "string of {len} bytes is longer than {max} bytes")
+            }
+            Self::Utf8(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for BoundedError {}
+
+impl<const MAX: usize> BoundedFastStr<MAX> {
+    /// The maximum length in bytes.
+    pub const MAX: usize = MAX;
+
+    /// Create a new `BoundedFastStr` by copying `text`, like [`FastStr::new`].
+    #[inline]
+    pub fn new<T: AsRef<str>>(text: T) -> Result<Self, BoundedError> {
+        let text = text.as_ref();
+        Self::check(text.len())?;
+        Ok(Self(FastStr::new(text)))
+    }
+
+    /// Create an empty `BoundedFastStr`.
+    #[inline]
+    pub const fn empty() -> Self {
+        Self(FastStr::empty())
+    }
+
+    /// Create a new `BoundedFastStr` from a static string slice.
+    ///
+    /// This panics if `s` is longer than `MAX`, which fails the build in a `const` context.
+    #[inline]
+    pub const fn from_static_str(s: &'static str) -> Self {
+        assert!(s.len() <= MAX, "string is longer than the bound");
+        Self(FastStr::from_static_str(s))
+    }
+
+    /// Create a new `BoundedFastStr` from a `String`, like [`FastStr::from_string`].
+    #[inline]
+    pub fn from_string(s: String) -> Result<Self, BoundedError> {
+        Self::check(s.len())?;
+        Ok(Self(FastStr::from_string(s)))
+    }
+
+    /// Create a new `BoundedFastStr` from a `Bytes`, like [`FastStr::from_bytes`].
+    ///
+    /// The length is checked before the bytes are validated.
+    #[inline]
+    pub fn from_bytes(b: Bytes) -> Result<Self, BoundedError> {
+        Self::check(b.len())?;
+        from_utf8(&b).map_err(BoundedError::Utf8)?;
+        // Safety: we have checked b is utf-8 valid
+        Ok(Self(unsafe { FastStr::from_bytes_unchecked(b) }))
+    }
+
+    #[inline]
+    pub(crate) fn check(len: usize) -> Result<(), BoundedError> {
+        if len > MAX {
+            return Err(BoundedError::TooLong { len, max: MAX });
+        }
+        Ok(())
+    }
+}
+
+impl<const MAX: usize> TryFrom<FastStr> for BoundedFastStr<MAX> {
+    type Error = BoundedError;
+
+    #[inline]
+    fn try_from(s: FastStr) -> Result<Self, Self::Error> {
+        Self::check(s.len())?;
+        Ok(Self(s))
+    }
+}
+
+impl<const MAX: usize> TryFrom<String> for BoundedFastStr<MAX> {
+    type Error = BoundedError;
+
+    #[inline]
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        Self::from_string(s)
+    }
+}
+
+impl<const MAX: usize> TryFrom<&'static str> for BoundedFastStr<MAX> {
+    type Error = BoundedError;
+
+    #[inline]
+    fn try_from(s: &'static str) -> Result<Self, Self::Error> {
+        Self::try_from(FastStr::from_static_str(s))
+    }
+}
+
+impl_faststr_newtype!([const MAX: usize] BoundedFastStr<MAX>, 0);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bounded() {
+        type Name = BoundedFastStr<8>;
+        assert_eq!(Name::new("short").unwrap(), "short");
+        assert_eq!(
+            Name::from_string(String::from("too long!")).unwrap_err(),
+            BoundedError::TooLong { len: 9, max: 8 }
+        );
+        assert!(matches!(
+            Name::from_bytes(Bytes::from_static(b"\xff")),
+            Err(BoundedError::Utf8(_))
+        ));
+        assert!(matches!(
+            Name::from_bytes(Bytes::from_static(b"\xff\xff\xff\xff\xff\xff\xff\xff\xff")),
+            Err(BoundedError::TooLong { .. })
+        ));
+        assert!(Name::try_from("12345678").is_ok());
+    }
+}
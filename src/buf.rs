@@ -0,0 +1,117 @@
+use alloc::{string::String, sync::Arc};
+
+use bytes::{Buf, Bytes};
+
+use crate::{FastStr, Repr, INLINE_CAP};
+
+/// A [`Buf`] reader over a `FastStr`, created by [`FastStr::into_buf`].
+///
+/// Reading never copies the string, and [`Buf::copy_to_bytes`] shares the underlying buffer
+/// whenever the representation of the `FastStr` allows it.
+#[derive(Clone, Debug)]
+pub struct FastStrBuf {
+    inner: FastStr,
+    pos: usize,
+}
+
+impl FastStrBuf {
+    #[inline]
+    pub(crate) fn new(inner: FastStr) -> Self {
+        Self { inner, pos: 0 }
+    }
+}
+
+impl From<FastStr> for FastStrBuf {
+    #[inline]
+    fn from(s: FastStr) -> Self {
+        Self::new(s)
+    }
+}
+
+impl Buf for FastStrBuf {
+    #[inline]
+    fn remaining(&self) -> usize {
+        self.inner.len() - self.pos
+    }
+
+    #[inline]
+    fn chunk(&self) -> &[u8] {
+        &self.inner.as_bytes()[self.pos..]
+    }
+
+    #[inline]
+    fn advance(&mut self, cnt: usize) {
+        assert!(
+            cnt <= self.remaining(),
+            "cannot advance past `remaining`: {:?} <= {:?}",
+            cnt,
+            self.remaining(),
+        );
+        self.pos += cnt;
+    }
+
+    fn copy_to_bytes(&mut self, len: usize) -> Bytes {
+        assert!(
+            len <= self.remaining(),
+            "`len` greater than remaining: {:?} <= {:?}",
+            len,
+            self.remaining(),
+        );
+        let range = self.pos..self.pos + len;
+        self.pos += len;
+        if len <= INLINE_CAP {
+            return Bytes::copy_from_slice(&self.inner.as_bytes()[range]);
+        }
+        match self.inner.0.uncached() {
+            // Safety: the subset is taken from `b` itself.
+            Repr::Bytes(b) => unsafe { b.slice_ref(&b[range]) }.into(),
+            Repr::StaticStr(s) => Bytes::from_static(&s.as_bytes()[range]),
+            Repr::ArcStr(s) => Bytes::from_owner(ArcOwner::Str(s.clone())).slice(range),
+            Repr::ArcString(s) => Bytes::from_owner(ArcOwner::String(s.clone())).slice(range),
+            // `uncached` never returns a cached repr.
+            Repr::Empty | Repr::Inline { .. } | Repr::Cached(_) => {
+                Bytes::copy_from_slice(&self.inner.as_bytes()[range])
+            }
+        }
+    }
+}
+
+/// Keeps a shared string alive as the owner of a `Bytes`.
+enum ArcOwner {
+    Str(Arc<str>),
+    String(Arc<String>),
+}
+
+impl AsRef<[u8]> for ArcOwner {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        match self {
+            Self::Str(s) => s.as_bytes(),
+            Self::String(s) => s.as_bytes(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_copy_to_bytes_shares() {
+        let text = "a string that is too long to be inlined";
+        for s in [
+            FastStr::from_static_str(text),
+            FastStr::from_arc_str(Arc::from(text)),
+            FastStr::from_arc_string(Arc::new(String::from(text))),
+            FastStr::from_string(String::from(text)),
+        ] {
+            let ptr = s.as_ptr();
+            let mut buf = s.into_buf();
+            buf.advance(2);
+            let b = buf.copy_to_bytes(text.len() - 2);
+            assert_eq!(b, text.as_bytes()[2..]);
+            assert_eq!(b.as_ptr(), ptr.wrapping_add(2));
+            assert!(!buf.has_remaining());
+        }
+    }
+}
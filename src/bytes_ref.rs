@@ -12,6 +12,8 @@ unsafe impl Sync for BytesRef {}
 pub struct BytesRef {
     pub(crate) ptr: *const u8,
     pub(crate) len: usize,
+    /// The owner of the bytes. The view is usually inside its visible bytes, but may be outside
+    /// them when `data` only keeps an allocation alive, like the chunks of an arena.
     pub(crate) data: Arc<Bytes>,
 }
 
@@ -38,10 +40,21 @@ impl From<BytesRef> for Bytes {
 
         let original_start = value.data.as_ptr();
         let offset = unsafe { value.ptr.offset_from(original_start) } as usize;
+        if offset + value.len > value.data.len() {
+            // The view is outside the visible bytes of `data`, like the strings of an arena chunk.
+            return Bytes::from_owner(value);
+        }
         value.data.slice(offset..offset + value.len)
     }
 }
 
+impl AsRef<[u8]> for BytesRef {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
 impl From<BytesRef> for Vec<u8> {
     #[inline]
     fn from(value: BytesRef) -> Self {
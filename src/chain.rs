@@ -0,0 +1,271 @@
+use alloc::{collections::VecDeque, string::String};
+use core::{fmt, iter};
+
+use bytes::Buf;
+
+use crate::FastStr;
+
+/// A rope-like sequence of `FastStr` segments that can be concatenated without copying.
+///
+/// Pushing a segment only clones the `FastStr`, and the chain can be written out through
+/// [`fmt::Display`] or [`Buf`] without building the concatenated string first. Call
+/// [`FastStrChain::flatten`] when a single `FastStr` is needed.
+///
+/// Consuming the chain through [`Buf::advance`] may stop in the middle of a character. The string
+/// views of the chain, such as [`FastStrChain::segments`], panic until the rest of that character
+/// has been consumed, while the [`Buf`] methods, comparisons and [`fmt::Debug`] keep working.
+#[derive(Clone, Default)]
+pub struct FastStrChain {
+    segments: VecDeque<FastStr>,
+    /// Bytes already consumed from the first segment.
+    offset: usize,
+    len: usize,
+}
+
+impl FastStrChain {
+    /// Create an empty chain.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            segments: VecDeque::new(),
+            offset: 0,
+            len: 0,
+        }
+    }
+
+    /// Create an empty chain with room for `capacity` segments.
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            segments: VecDeque::with_capacity(capacity),
+            offset: 0,
+            len: 0,
+        }
+    }
+
+    /// Append a segment to the end of the chain.
+    #[inline]
+    pub fn push<S: Into<FastStr>>(&mut self, s: S) {
+        let s = s.into();
+        if s.is_empty() {
+            return;
+        }
+        self.len += s.len();
+        self.segments.push_back(s);
+    }
+
+    /// Return the total length in bytes of the chain.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Return `true` if the chain is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Return an iterator over the segments of the chain.
+    #[inline]
+    pub fn segments(&self) -> impl DoubleEndedIterator<Item = &str> + '_ {
+        let offset = self.offset;
+        self.segments
+            .iter()
+            .enumerate()
+            .map(move |(i, s)| if i == 0 { &s[offset..] } else { s.as_str() })
+    }
+
+    /// Return an iterator over the bytes of each segment, which never panics.
+    #[inline]
+    fn byte_segments(&self) -> impl Iterator<Item = &[u8]> + '_ {
+        let offset = self.offset;
+        self.segments.iter().enumerate().map(move |(i, s)| {
+            if i == 0 {
+                &s.as_bytes()[offset..]
+            } else {
+                s.as_bytes()
+            }
+        })
+    }
+
+    /// Return an iterator over the chars of the chain.
+    #[inline]
+    pub fn chars(&self) -> impl Iterator<Item = char> + '_ {
+        self.segments().flat_map(str::chars)
+    }
+
+    /// Concatenate the segments into a single `FastStr`.
+    ///
+    /// A chain of a single segment is returned without copying.
+    pub fn flatten(&self) -> FastStr {
+        match self.segments.len() {
+            0 => FastStr::empty(),
+            1 => {
+                let s = &self.segments[0];
+                s.slice_ref(&s[self.offset..])
+            }
+            _ => self.segments().collect(),
+        }
+    }
+}
+
+impl fmt::Display for FastStrChain {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.segments().try_for_each(|s| f.write_str(s))
+    }
+}
+
+impl fmt::Debug for FastStrChain {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list()
+            .entries(self.byte_segments().map(String::from_utf8_lossy))
+            .finish()
+    }
+}
+
+impl PartialEq<str> for FastStrChain {
+    fn eq(&self, other: &str) -> bool {
+        if self.len != other.len() {
+            return false;
+        }
+        let mut rest = other.as_bytes();
+        self.byte_segments().all(|s| {
+            let (head, tail) = rest.split_at(s.len());
+            rest = tail;
+            head == s
+        })
+    }
+}
+
+impl<'a> PartialEq<&'a str> for FastStrChain {
+    #[inline]
+    fn eq(&self, other: &&'a str) -> bool {
+        self == *other
+    }
+}
+
+impl PartialEq<FastStr> for FastStrChain {
+    #[inline]
+    fn eq(&self, other: &FastStr) -> bool {
+        self == other.as_str()
+    }
+}
+
+impl PartialEq for FastStrChain {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len
+            && self
+                .byte_segments()
+                .flatten()
+                .eq(other.byte_segments().flatten())
+    }
+}
+
+impl Eq for FastStrChain {}
+
+impl<S: Into<FastStr>> Extend<S> for FastStrChain {
+    fn extend<I: IntoIterator<Item = S>>(&mut self, iter: I) {
+        for s in iter {
+            self.push(s);
+        }
+    }
+}
+
+impl<S: Into<FastStr>> iter::FromIterator<S> for FastStrChain {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = S>>(iter: I) -> Self {
+        let mut chain = Self::new();
+        chain.extend(iter);
+        chain
+    }
+}
+
+impl Buf for FastStrChain {
+    #[inline]
+    fn remaining(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    fn chunk(&self) -> &[u8] {
+        match self.segments.front() {
+            Some(s) => &s.as_bytes()[self.offset..],
+            None => &[],
+        }
+    }
+
+    fn advance(&mut self, mut cnt: usize) {
+        assert!(
+            cnt <= self.len,
+            "cannot advance past `remaining`: {:?} <= {:?}",
+            cnt,
+            self.len,
+        );
+        self.len -= cnt;
+        while cnt > 0 {
+            let front = self.segments[0].len() - self.offset;
+            if cnt < front {
+                self.offset += cnt;
+                return;
+            }
+            cnt -= front;
+            self.offset = 0;
+            self.segments.pop_front();
+        }
+    }
+
+    #[cfg(feature = "std")]
+    fn chunks_vectored<'a>(&'a self, dst: &mut [std::io::IoSlice<'a>]) -> usize {
+        let mut n = 0;
+        for (slot, s) in dst.iter_mut().zip(self.byte_segments()) {
+            *slot = std::io::IoSlice::new(s);
+            n += 1;
+        }
+        n
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chain() {
+        let mut chain: FastStrChain = ["Hello", ", ", ""].into_iter().collect();
+        chain.push(FastStr::from_string("wörld".repeat(10)));
+        let expected = alloc::format!("Hello, {}", "wörld".repeat(10));
+        assert_eq!(chain.len(), expected.len());
+        assert_eq!(chain, expected.as_str());
+        assert_eq!(alloc::format!("{chain}"), expected);
+        assert!(chain.chars().eq(expected.chars()));
+        assert_eq!(chain.flatten(), expected);
+
+        chain.advance(3);
+        assert_eq!(chain.chunk(), b"lo");
+        chain.advance(4);
+        assert_eq!(chain, &expected[7..]);
+        assert_eq!(chain.segments().count(), 1);
+        assert_eq!(chain.flatten().as_ptr(), chain.chunk().as_ptr());
+        assert_eq!(
+            chain.copy_to_bytes(chain.remaining()),
+            &expected.as_bytes()[7..]
+        );
+        assert!(chain.is_empty());
+    }
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_advance_into_char() {
+        let mut chain: FastStrChain = ["wörld", "!"].into_iter().collect();
+        chain.advance(2);
+        assert_eq!(chain.chunk(), &"wörld".as_bytes()[2..]);
+        let mut slices = [std::io::IoSlice::new(&[]); 4];
+        assert_eq!(chain.chunks_vectored(&mut slices), 2);
+        assert_eq!(&*slices[0], &"wörld".as_bytes()[2..]);
+        assert_eq!(&*slices[1], b"!");
+        assert_eq!(alloc::format!("{chain:?}"), "[\"\u{fffd}rld\", \"!\"]");
+        assert_eq!(chain, chain.clone());
+        chain.advance(1);
+        assert_eq!(chain, "rld!");
+    }
+}
@@ -0,0 +1,225 @@
+use alloc::{
+    boxed::Box,
+    collections::{BTreeMap, VecDeque},
+    vec::Vec,
+};
+
+use crate::{stats::CopyPath, FastStr, Repr};
+
+/// Decides when [`FastStr::compact_if`] copies a string out of its backing buffer.
+///
+/// A string is compacted when it wastes at least `min_wasted_bytes` bytes, and its
+/// [`retained_ratio`](FastStr::retained_ratio) is at least `min_retained_ratio`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CompactPolicy {
+    /// The minimum of `backing_len() - len()` for a string to be compacted.
+    pub min_wasted_bytes: usize,
+    /// The minimum of `retained_ratio()` for a string to be compacted.
+    pub min_retained_ratio: f64,
+}
+
+impl CompactPolicy {
+    /// A policy that compacts every string that wastes any memory.
+    pub const ALWAYS: Self = Self::new(1, 1.0);
+
+    /// Create a new policy.
+    #[inline]
+    pub const fn new(min_wasted_bytes: usize, min_retained_ratio: f64) -> Self {
+        Self {
+            min_wasted_bytes,
+            min_retained_ratio,
+        }
+    }
+
+    /// Return `true` if `s` should be compacted under this policy.
+    #[inline]
+    pub fn should_compact(&self, s: &FastStr) -> bool {
+        let wasted = s.backing_len() - s.len();
+        wasted > 0
+            && wasted >= self.min_wasted_bytes
+            && s.retained_ratio() >= self.min_retained_ratio
+    }
+}
+
+impl Default for CompactPolicy {
+    /// Compact strings that waste at least 1 KiB and keep alive at least twice their size.
+    #[inline]
+    fn default() -> Self {
+        Self::new(1024, 2.0)
+    }
+}
+
+impl FastStr {
+    /// Return the size in bytes of the buffer kept alive by this `FastStr`.
+    ///
+    /// For a string sliced out of a larger [`Bytes`](bytes::Bytes), this is the size of the
+    /// whole `Bytes`, and for an `Arc<String>` it is the capacity of the `String`. Strings that
+    /// are inline or static report their own length.
+    #[inline]
+    pub fn backing_len(&self) -> usize {
+        self.0.backing_len()
+    }
+
+    /// Return the ratio of [`FastStr::backing_len`] to [`FastStr::len`].
+    ///
+    /// A ratio of `1.0` means that no memory is wasted.
+    #[inline]
+    pub fn retained_ratio(&self) -> f64 {
+        if self.is_empty() {
+            return 1.0;
+        }
+        self.backing_len() as f64 / self.len() as f64
+    }
+
+    /// Copy the string into a buffer of its own if it wastes enough memory under `policy`.
+    ///
+    /// Returns `true` if the string has been copied.
+    #[inline]
+    pub fn compact_if(&mut self, policy: &CompactPolicy) -> bool {
+        if !policy.should_compact(self) {
+            return false;
+        }
+        *self = Self(Repr::copy_of(self.as_str(), CopyPath::DeepClone));
+        true
+    }
+}
+
+/// A type that can hand out mutable references to every `FastStr` it contains.
+///
+/// Implement this for structs holding `FastStr` fields to compact all of them at once with
+/// [`compact_all`].
+///
+/// ```
+/// use faststr::{CompactPolicy, FastStr, VisitFastStr};
+///
+/// struct Entry {
+///     key: FastStr,
+///     tags: Vec<FastStr>,
+/// }
+///
+/// impl VisitFastStr for Entry {
+///     fn visit_faststr_mut(&mut self, f: &mut dyn FnMut(&mut FastStr)) {
+///         self.key.visit_faststr_mut(f);
+///         self.tags.visit_faststr_mut(f);
+///     }
+/// }
+///
+/// let mut entry = Entry {
+///     key: FastStr::new("key"),
+///     tags: vec![],
+/// };
+/// faststr::compact_all(&mut entry, &CompactPolicy::default());
+/// ```
+pub trait VisitFastStr {
+    /// Call `f` with every `FastStr` contained in `self`.
+    fn visit_faststr_mut(&mut self, f: &mut dyn FnMut(&mut FastStr));
+}
+
+/// Compact every `FastStr` in `value` with [`FastStr::compact_if`], returning the number of
+/// strings that have been copied.
+pub fn compact_all<T: VisitFastStr + ?Sized>(value: &mut T, policy: &CompactPolicy) -> usize {
+    let mut compacted = 0;
+    value.visit_faststr_mut(&mut |s| {
+        if s.compact_if(policy) {
+            compacted += 1;
+        }
+    });
+    compacted
+}
+
+impl VisitFastStr for FastStr {
+    #[inline]
+    fn visit_faststr_mut(&mut self, f: &mut dyn FnMut(&mut FastStr)) {
+        f(self)
+    }
+}
+
+impl<T: VisitFastStr + ?Sized> VisitFastStr for Box<T> {
+    #[inline]
+    fn visit_faststr_mut(&mut self, f: &mut dyn FnMut(&mut FastStr)) {
+        (**self).visit_faststr_mut(f)
+    }
+}
+
+impl<T: VisitFastStr> VisitFastStr for Option<T> {
+    #[inline]
+    fn visit_faststr_mut(&mut self, f: &mut dyn FnMut(&mut FastStr)) {
+        if let Some(v) = self {
+            v.visit_faststr_mut(f)
+        }
+    }
+}
+
+impl<T: VisitFastStr> VisitFastStr for [T] {
+    #[inline]
+    fn visit_faststr_mut(&mut self, f: &mut dyn FnMut(&mut FastStr)) {
+        self.iter_mut().for_each(|v| v.visit_faststr_mut(f))
+    }
+}
+
+impl<T: VisitFastStr, const N: usize> VisitFastStr for [T; N] {
+    #[inline]
+    fn visit_faststr_mut(&mut self, f: &mut dyn FnMut(&mut FastStr)) {
+        self.as_mut_slice().visit_faststr_mut(f)
+    }
+}
+
+impl<T: VisitFastStr> VisitFastStr for Vec<T> {
+    #[inline]
+    fn visit_faststr_mut(&mut self, f: &mut dyn FnMut(&mut FastStr)) {
+        self.as_mut_slice().visit_faststr_mut(f)
+    }
+}
+
+impl<T: VisitFastStr> VisitFastStr for VecDeque<T> {
+    #[inline]
+    fn visit_faststr_mut(&mut self, f: &mut dyn FnMut(&mut FastStr)) {
+        self.iter_mut().for_each(|v| v.visit_faststr_mut(f))
+    }
+}
+
+/// Only the values are visited, because keys cannot be mutated in place.
+impl<K, V: VisitFastStr> VisitFastStr for BTreeMap<K, V> {
+    #[inline]
+    fn visit_faststr_mut(&mut self, f: &mut dyn FnMut(&mut FastStr)) {
+        self.values_mut().for_each(|v| v.visit_faststr_mut(f))
+    }
+}
+
+/// Only the values are visited, because keys cannot be mutated in place.
+#[cfg(feature = "std")]
+impl<K, V: VisitFastStr, S> VisitFastStr for std::collections::HashMap<K, V, S> {
+    #[inline]
+    fn visit_faststr_mut(&mut self, f: &mut dyn FnMut(&mut FastStr)) {
+        self.values_mut().for_each(|v| v.visit_faststr_mut(f))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use bytes::Bytes;
+
+    use super::*;
+
+    #[test]
+    fn test_compact() {
+        let text = "x".repeat(4096);
+        let whole = FastStr::from_bytes(Bytes::from(text)).unwrap();
+        let mut slices = vec![
+            whole.slice_ref(&whole[..40]),
+            whole.slice_ref(&whole[..4000]),
+            whole.slice_ref(&whole[..10]),
+        ];
+        assert_eq!(slices[0].backing_len(), 4096);
+        assert_eq!(slices[0].retained_ratio(), 4096.0 / 40.0);
+        assert_eq!(slices[2].retained_ratio(), 1.0);
+
+        assert_eq!(compact_all(&mut slices, &CompactPolicy::default()), 1);
+        assert_eq!(slices[0].backing_len(), 40);
+        assert_eq!(slices[1].backing_len(), 4096);
+        assert_eq!(slices[0], whole[..40]);
+        assert!(!whole.clone().compact_if(&CompactPolicy::ALWAYS));
+    }
+}
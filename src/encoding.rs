@@ -0,0 +1,261 @@
+use alloc::{string::String, vec, vec::Vec};
+use core::fmt;
+
+use bytes::Bytes;
+
+use crate::{AsciiFastStr, FastStr, Repr, INLINE_CAP};
+
+const HEX: &[u8; 16] = b"0123456789abcdef";
+
+/// The alphabet and padding used by [`FastStr::encode_base64`] and [`FastStr::decode_base64`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Base64Alphabet {
+    /// The standard alphabet from RFC 4648, with `=` padding.
+    Standard,
+    /// The standard alphabet, without padding.
+    StandardNoPad,
+    /// The URL and filename safe alphabet from RFC 4648, with `=` padding.
+    UrlSafe,
+    /// The URL and filename safe alphabet, without padding.
+    UrlSafeNoPad,
+}
+
+impl Base64Alphabet {
+    #[inline]
+    fn symbols(self) -> &'static [u8; 64] {
+        match self {
+            Self::Standard | Self::StandardNoPad => {
+                b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/"
+            }
+            Self::UrlSafe | Self::UrlSafeNoPad => {
+                b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_"
+            }
+        }
+    }
+
+    #[inline]
+    fn padded(self) -> bool {
+        matches!(self, Self::Standard | Self::UrlSafe)
+    }
+
+    #[inline]
+    fn decode(self, b: u8) -> Option<u8> {
+        match b {
+            b'A'..=b'Z' => Some(b - b'A'),
+            b'a'..=b'z' => Some(b - b'a' + 26),
+            b'0'..=b'9' => Some(b - b'0' + 52),
+            _ => self.symbols()[62..]
+                .iter()
+                .position(|&s| s == b)
+                .map(|i| 62 + i as u8),
+        }
+    }
+}
+
+/// The error returned when decoding hex or base64 fails.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DecodeError {
+    /// The length of the input is not valid for the encoding.
+    InvalidLength,
+    /// The byte at this offset is not valid for the encoding.
+    InvalidByte(usize),
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidLength => f.write_str("invalid input length"),
+            Self::InvalidByte(offset) => write!(f, "invalid byte at offset {offset}"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DecodeError {}
+
+impl FastStr {
+    /// Encode `data` as lowercase hex.
+    ///
+    /// The output is written straight into an inline `FastStr` when it fits, and into a single
+    /// allocation otherwise.
+    pub fn encode_hex(data: &[u8]) -> AsciiFastStr {
+        build_ascii(data.len() * 2, |out| {
+            for (pair, &b) in out.chunks_exact_mut(2).zip(data) {
+                pair[0] = HEX[(b >> 4) as usize];
+                pair[1] = HEX[(b & 0xf) as usize];
+            }
+        })
+    }
+
+    /// Encode `data` as base64 with `alphabet`.
+    ///
+    /// The output is written straight into an inline `FastStr` when it fits, and into a single
+    /// allocation otherwise.
+    pub fn encode_base64(data: &[u8], alphabet: Base64Alphabet) -> AsciiFastStr {
+        let symbols = alphabet.symbols();
+        let len = if alphabet.padded() {
+            data.len().div_ceil(3) * 4
+        } else {
+            (data.len() * 4).div_ceil(3)
+        };
+        build_ascii(len, |out| {
+            for (group, chunk) in out.chunks_mut(4).zip(data.chunks(3)) {
+                let n = (chunk[0] as u32) << 16
+                    | (*chunk.get(1).unwrap_or(&0) as u32) << 8
+                    | *chunk.get(2).unwrap_or(&0) as u32;
+                let encoded = [18, 12, 6, 0].map(|shift| symbols[(n >> shift) as usize & 0x3f]);
+                // Only `chunk.len() + 1` symbols carry data, the rest of a group is padding.
+                let used = (chunk.len() + 1).min(group.len());
+                group[..used].copy_from_slice(&encoded[..used]);
+                group[used..].fill(b'=');
+            }
+        })
+    }
+
+    /// Decode the string as hex, in either case.
+    // `usize::is_multiple_of` needs Rust 1.87.
+    #[allow(clippy::manual_is_multiple_of)]
+    pub fn decode_hex(&self) -> Result<Bytes, DecodeError> {
+        let input = self.as_bytes();
+        if input.len() % 2 != 0 {
+            return Err(DecodeError::InvalidLength);
+        }
+        let digit = |i: usize| {
+            (input[i] as char)
+                .to_digit(16)
+                .map(|d| d as u8)
+                .ok_or(DecodeError::InvalidByte(i))
+        };
+        let mut out = Vec::with_capacity(input.len() / 2);
+        for i in (0..input.len()).step_by(2) {
+            out.push(digit(i)? << 4 | digit(i + 1)?);
+        }
+        Ok(out.into())
+    }
+
+    /// Decode the string as base64 with `alphabet`.
+    ///
+    /// Padding is required for the padded alphabets, and rejected for the others. Encodings whose
+    /// unused trailing bits are not zero are rejected, so that every input decodes from exactly
+    /// one string.
+    #[allow(clippy::manual_is_multiple_of)]
+    pub fn decode_base64(&self, alphabet: Base64Alphabet) -> Result<Bytes, DecodeError> {
+        let mut input = self.as_bytes();
+        if alphabet.padded() {
+            if input.len() % 4 != 0 {
+                return Err(DecodeError::InvalidLength);
+            }
+            for _ in 0..2 {
+                input = input.strip_suffix(b"=").unwrap_or(input);
+            }
+        }
+        if input.len() % 4 == 1 {
+            return Err(DecodeError::InvalidLength);
+        }
+        let mut out = Vec::with_capacity(input.len() * 3 / 4);
+        for (i, chunk) in input.chunks(4).enumerate() {
+            let mut n = 0u32;
+            for (j, &b) in chunk.iter().enumerate() {
+                let v = alphabet
+                    .decode(b)
+                    .ok_or(DecodeError::InvalidByte(i * 4 + j))?;
+                n |= (v as u32) << (18 - 6 * j);
+            }
+            if n & (0xff_ffff >> (8 * (chunk.len() - 1))) != 0 {
+                return Err(DecodeError::InvalidByte(i * 4 + chunk.len() - 1));
+            }
+            out.extend_from_slice(&n.to_be_bytes()[1..chunk.len()]);
+        }
+        Ok(out.into())
+    }
+}
+
+/// Create an ASCII string of `len` bytes, filled in place by `fill`.
+fn build_ascii(len: usize, fill: impl FnOnce(&mut [u8])) -> AsciiFastStr {
+    let s = if len == 0 {
+        FastStr::empty()
+    } else if len <= INLINE_CAP {
+        let mut buf = [0; INLINE_CAP];
+        fill(&mut buf[..len]);
+        FastStr(Repr::inline(len, buf))
+    } else {
+        let mut v = vec![0; len];
+        fill(&mut v);
+        // Safety: `fill` only writes ASCII.
+        FastStr(Repr::from_string(unsafe { String::from_utf8_unchecked(v) }))
+    };
+    // Safety: `fill` only writes hex or base64 digits, which are printable ASCII.
+    unsafe { AsciiFastStr::new_unchecked(s) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ReprKind;
+
+    #[test]
+    fn test_hex() {
+        let id = [0xde, 0xad, 0xbe, 0xef, 0x00, 0x01, 0x02, 0x03];
+        let hex = FastStr::encode_hex(&id);
+        assert_eq!(hex, "deadbeef00010203");
+        assert!(hex.as_faststr().is_inline());
+        assert_eq!(hex.as_faststr().decode_hex().unwrap(), &id[..]);
+        assert_eq!(FastStr::new("DEADbeef").decode_hex().unwrap(), &id[..4]);
+        assert_eq!(
+            FastStr::new("abc").decode_hex(),
+            Err(DecodeError::InvalidLength)
+        );
+        assert_eq!(
+            FastStr::new("0g").decode_hex(),
+            Err(DecodeError::InvalidByte(1))
+        );
+        assert!(!FastStr::encode_hex(&[0; 16]).as_faststr().is_inline());
+        assert_eq!(
+            FastStr::encode_hex(&[]).as_faststr().repr_kind(),
+            ReprKind::Empty
+        );
+    }
+
+    #[test]
+    fn test_base64() {
+        for (data, padded, url_no_pad) in [
+            (&b""[..], "", ""),
+            (b"f", "Zg==", "Zg"),
+            (b"fo", "Zm8=", "Zm8"),
+            (b"foo", "Zm9v", "Zm9v"),
+            (b"\xfb\xff", "+/8=", "-_8"),
+        ] {
+            let encoded = FastStr::encode_base64(data, Base64Alphabet::Standard);
+            assert_eq!(encoded, padded);
+            assert_eq!(
+                encoded.as_faststr().decode_base64(Base64Alphabet::Standard),
+                Ok(Bytes::copy_from_slice(data))
+            );
+            let encoded = FastStr::encode_base64(data, Base64Alphabet::UrlSafeNoPad);
+            assert_eq!(encoded, url_no_pad);
+            assert_eq!(
+                encoded
+                    .as_faststr()
+                    .decode_base64(Base64Alphabet::UrlSafeNoPad),
+                Ok(Bytes::copy_from_slice(data))
+            );
+        }
+        assert_eq!(
+            FastStr::new("Zg=").decode_base64(Base64Alphabet::Standard),
+            Err(DecodeError::InvalidLength)
+        );
+        assert_eq!(
+            FastStr::new("Zg==").decode_base64(Base64Alphabet::StandardNoPad),
+            Err(DecodeError::InvalidByte(2))
+        );
+        assert_eq!(
+            FastStr::new("Zh==").decode_base64(Base64Alphabet::Standard),
+            Err(DecodeError::InvalidByte(1))
+        );
+        assert_eq!(
+            FastStr::new("Zm9=").decode_base64(Base64Alphabet::Standard),
+            Err(DecodeError::InvalidByte(2))
+        );
+    }
+}
@@ -0,0 +1,266 @@
+use alloc::string::String;
+use core::fmt;
+
+use crate::{FastStr, Repr, INLINE_CAP};
+
+impl FastStr {
+    /// Escape the string for use inside a JSON string literal, without the surrounding quotes.
+    ///
+    /// `"`, `\` and control characters are escaped. The string is cloned without copying if
+    /// none of them is present.
+    #[inline]
+    pub fn json_escape(&self) -> Self {
+        self.escape(
+            |b| (b < 0x20) | (b == b'"') | (b == b'\\'),
+            |b, out| match b {
+                b'"' => out.push_str("\\\""),
+                b'\\' => out.push_str("\\\\"),
+                b'\n' => out.push_str("\\n"),
+                b'\r' => out.push_str("\\r"),
+                b'\t' => out.push_str("\\t"),
+                0x08 => out.push_str("\\b"),
+                0x0c => out.push_str("\\f"),
+                _ => {
+                    const HEX: &[u8; 16] = b"0123456789abcdef";
+                    let esc = [
+                        b'\\',
+                        b'u',
+                        b'0',
+                        b'0',
+                        HEX[(b >> 4) as usize],
+                        HEX[(b & 0xf) as usize],
+                    ];
+                    // Safety: the escape is ASCII.
+                    out.push_str(unsafe { core::str::from_utf8_unchecked(&esc) })
+                }
+            },
+        )
+    }
+
+    /// Resolve the escapes of the content of a JSON string literal, without the surrounding
+    /// quotes.
+    ///
+    /// The string is cloned without copying if it has no escape. Unescaped control characters
+    /// are not rejected.
+    pub fn json_unescape(&self) -> Result<Self, JsonUnescapeError> {
+        let bytes = self.as_bytes();
+        let Some(first) = bytes.iter().position(|&b| b == b'\\') else {
+            return Ok(self.clone());
+        };
+        let mut out = Builder::with_capacity(bytes.len());
+        let mut start = 0;
+        let mut i = first;
+        while i < bytes.len() {
+            if bytes[i] != b'\\' {
+                i += 1;
+                continue;
+            }
+            out.push_str(&self[start..i]);
+            let err = JsonUnescapeError { offset: i };
+            let c = match bytes.get(i + 1).ok_or(err)? {
+                b'"' => '"',
+                b'\\' => '\\',
+                b'/' => '/',
+                b'b' => '\u{8}',
+                b'f' => '\u{c}',
+                b'n' => '\n',
+                b'r' => '\r',
+                b't' => '\t',
+                b'u' => {
+                    let high = hex4(bytes, i + 2).ok_or(err)?;
+                    let code = if (0xd800..0xdc00).contains(&high) {
+                        if bytes.get(i + 6..i + 8) != Some(b"\\u") {
+                            return Err(err);
+                        }
+                        let low = hex4(bytes, i + 8).ok_or(err)?;
+                        if !(0xdc00..0xe000).contains(&low) {
+                            return Err(err);
+                        }
+                        i += 6;
+                        0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00)
+                    } else {
+                        high
+                    };
+                    i += 4;
+                    char::from_u32(code).ok_or(err)?
+                }
+                _ => return Err(err),
+            };
+            out.push(c);
+            i += 2;
+            start = i;
+        }
+        out.push_str(&self[start..]);
+        Ok(out.finish())
+    }
+
+    /// Escape `&`, `<`, `>`, `"` and `'` for use in HTML text or attribute values.
+    ///
+    /// The string is cloned without copying if none of them is present.
+    #[inline]
+    pub fn html_escape(&self) -> Self {
+        self.escape(is_markup, |b, out| {
+            out.push_str(match b {
+                b'&' => "&amp;",
+                b'<' => "&lt;",
+                b'>' => "&gt;",
+                b'"' => "&quot;",
+                _ => "&#x27;",
+            })
+        })
+    }
+
+    /// Escape `&`, `<`, `>`, `"` and `'` with the predefined XML entities.
+    ///
+    /// The string is cloned without copying if none of them is present.
+    #[inline]
+    pub fn xml_escape(&self) -> Self {
+        self.escape(is_markup, |b, out| {
+            out.push_str(match b {
+                b'&' => "&amp;",
+                b'<' => "&lt;",
+                b'>' => "&gt;",
+                b'"' => "&quot;",
+                _ => "&apos;",
+            })
+        })
+    }
+
+    /// Replace every byte matching `needs` with the output of `escape`. The bytes matched must be
+    /// ASCII.
+    fn escape(&self, needs: fn(u8) -> bool, escape: fn(u8, &mut Builder)) -> Self {
+        let bytes = self.as_bytes();
+        let Some(first) = bytes.iter().position(|&b| needs(b)) else {
+            return self.clone();
+        };
+        let mut out = Builder::with_capacity(bytes.len() + bytes.len() / 8);
+        let mut start = 0;
+        for (i, &b) in bytes.iter().enumerate().skip(first) {
+            if needs(b) {
+                out.push_str(&self[start..i]);
+                escape(b, &mut out);
+                start = i + 1;
+            }
+        }
+        out.push_str(&self[start..]);
+        out.finish()
+    }
+}
+
+/// The error returned by [`FastStr::json_unescape`] for an invalid escape sequence.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct JsonUnescapeError {
+    offset: usize,
+}
+
+impl JsonUnescapeError {
+    /// Return the byte offset of the invalid escape sequence.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+impl fmt::Display for JsonUnescapeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid escape sequence at byte offset {}", self.offset)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for JsonUnescapeError {}
+
+#[inline]
+fn is_markup(b: u8) -> bool {
+    (b == b'&') | (b == b'<') | (b == b'>') | (b == b'"') | (b == b'\'')
+}
+
+fn hex4(bytes: &[u8], at: usize) -> Option<u32> {
+    let digits = bytes.get(at..at + 4)?;
+    digits
+        .iter()
+        .try_fold(0, |acc, &b| Some(acc << 4 | (b as char).to_digit(16)?))
+}
+
+/// Builds a string on the stack, and only moves it to the heap once it outgrows `INLINE_CAP`.
+struct Builder {
+    len: usize,
+    buf: [u8; INLINE_CAP],
+    heap: Option<String>,
+    capacity: usize,
+}
+
+impl Builder {
+    #[inline]
+    fn with_capacity(capacity: usize) -> Self {
+        Self {
+            len: 0,
+            buf: [0; INLINE_CAP],
+            heap: None,
+            capacity,
+        }
+    }
+
+    fn push_str(&mut self, s: &str) {
+        if let Some(heap) = &mut self.heap {
+            heap.push_str(s);
+        } else if self.len + s.len() <= INLINE_CAP {
+            self.buf[self.len..][..s.len()].copy_from_slice(s.as_bytes());
+            self.len += s.len();
+        } else {
+            let mut heap = String::with_capacity(self.capacity.max(self.len + s.len()));
+            // Safety: only string slices have been copied into `buf`.
+            heap.push_str(unsafe { core::str::from_utf8_unchecked(&self.buf[..self.len]) });
+            heap.push_str(s);
+            self.heap = Some(heap);
+        }
+    }
+
+    #[inline]
+    fn push(&mut self, c: char) {
+        self.push_str(c.encode_utf8(&mut [0; 4]))
+    }
+
+    #[inline]
+    fn finish(self) -> FastStr {
+        match self.heap {
+            Some(s) => FastStr(Repr::from_string(s)),
+            None => FastStr(Repr::inline(self.len, self.buf)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json() {
+        let clean = FastStr::from_string("a string that is too long to be inlined".into());
+        assert_eq!(clean.json_escape().as_ptr(), clean.as_ptr());
+        assert_eq!(clean.json_unescape().unwrap().as_ptr(), clean.as_ptr());
+
+        let raw = FastStr::new("say \"hi\"\n\u{1}\\ ☃ 𝄞");
+        let escaped = raw.json_escape();
+        assert_eq!(escaped, r#"say \"hi\"\n\u0001\\ ☃ 𝄞"#);
+        assert_eq!(escaped.json_unescape().unwrap(), raw);
+        assert_eq!(FastStr::new(r"☃ 𝄞 \/").json_unescape().unwrap(), "☃ 𝄞 /");
+        for bad in [r"\", r"\x", r"\u12", r"\ud834", r"\ud834A", r"\udd1e"] {
+            assert!(FastStr::new(bad).json_unescape().is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    fn test_markup() {
+        let s = FastStr::new("<a href=\"x\">Tom & Jerry's</a>");
+        assert_eq!(
+            s.html_escape(),
+            "&lt;a href=&quot;x&quot;&gt;Tom &amp; Jerry&#x27;s&lt;/a&gt;"
+        );
+        assert_eq!(
+            s.xml_escape(),
+            "&lt;a href=&quot;x&quot;&gt;Tom &amp; Jerry&apos;s&lt;/a&gt;"
+        );
+        assert!(FastStr::new("<>").xml_escape().is_inline());
+    }
+}
@@ -0,0 +1,118 @@
+use alloc::{string::String, sync::Arc};
+use core::mem::size_of;
+
+use bytes::Bytes;
+
+use crate::{CachedRepr, FastStr, Repr};
+
+/// The size of the reference counts in front of the value of an `Arc`.
+const ARC_HEADER: usize = 2 * size_of::<usize>();
+
+/// The size of the allocation holding the cached metadata of a string.
+const CACHED_HEADER: usize = ARC_HEADER + size_of::<CachedRepr>();
+
+impl FastStr {
+    /// Return the heap memory used by this `FastStr`, with shared allocations divided evenly
+    /// between all their references.
+    ///
+    /// Inline and static strings use no heap memory. The size of a `Bytes` buffer is only known
+    /// through this `FastStr`, so clones of that `Bytes` held elsewhere are not taken into
+    /// account.
+    ///
+    /// The buffer of a string returned by [`FastStr::cache_char_metadata`] is shared with the
+    /// string it was created from, and the cache itself is shared by all the clones of the cached
+    /// string.
+    #[inline]
+    pub fn heap_size(&self) -> usize {
+        heap_size(&self.0)
+    }
+
+    /// Return the heap memory kept alive by this `FastStr`, including the memory shared with
+    /// other references.
+    #[inline]
+    pub fn exclusive_heap_size(&self) -> usize {
+        exclusive_heap_size(&self.0)
+    }
+}
+
+fn heap_size(repr: &Repr) -> usize {
+    match repr {
+        Repr::Cached(c) => (CACHED_HEADER + heap_size(&c.repr)) / Arc::strong_count(c),
+        repr => match repr.ref_count() {
+            Some(count) => exclusive_heap_size(repr) / count,
+            None => 0,
+        },
+    }
+}
+
+fn exclusive_heap_size(repr: &Repr) -> usize {
+    match repr {
+        Repr::Bytes(b) => ARC_HEADER + size_of::<Bytes>() + b.data.len().max(b.len),
+        Repr::ArcStr(s) => ARC_HEADER + s.len(),
+        Repr::ArcString(s) => ARC_HEADER + size_of::<String>() + s.capacity(),
+        Repr::Cached(c) => CACHED_HEADER + exclusive_heap_size(&c.repr),
+        Repr::Empty | Repr::StaticStr(_) | Repr::Inline { .. } => 0,
+    }
+}
+
+/// A weigher for caches keyed and valued by `FastStr`, such as
+/// `moka::sync::Cache::builder().weigher(faststr::weigher)`.
+///
+/// The weight is the size of both values plus the memory they keep alive, as reported by
+/// [`FastStr::exclusive_heap_size`], saturated to `u32::MAX`.
+#[inline]
+pub fn weigher(key: &FastStr, value: &FastStr) -> u32 {
+    let size = 2 * size_of::<FastStr>() + key.exclusive_heap_size() + value.exclusive_heap_size();
+    size.try_into().unwrap_or(u32::MAX)
+}
+
+#[cfg(feature = "get-size")]
+impl get_size::GetSize for FastStr {
+    #[inline]
+    fn get_heap_size(&self) -> usize {
+        self.heap_size()
+    }
+}
+
+#[cfg(feature = "deepsize")]
+impl deepsize::DeepSizeOf for FastStr {
+    /// Shared `Arc` strings are counted once per context, while `Bytes` buffers are divided
+    /// evenly between their references like [`FastStr::heap_size`].
+    fn deep_size_of_children(&self, context: &mut deepsize::Context) -> usize {
+        match &self.0 {
+            Repr::ArcStr(s) => deepsize::DeepSizeOf::deep_size_of_children(s, context),
+            Repr::ArcString(s) => deepsize::DeepSizeOf::deep_size_of_children(s, context),
+            _ => self.heap_size(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_heap_size() {
+        assert_eq!(FastStr::new("inline").heap_size(), 0);
+        assert_eq!(FastStr::from_static_str("static").heap_size(), 0);
+
+        let s = FastStr::from_string("x".repeat(100));
+        let size = s.exclusive_heap_size();
+        assert!(size >= 100);
+        assert_eq!(s.heap_size(), size);
+        let cloned = s.clone();
+        assert_eq!(cloned.heap_size(), size / 2);
+        assert_eq!(weigher(&s, &cloned), 2 * (32 + size as u32));
+
+        let x = FastStr::from_string("x".repeat(40));
+        let y = x.clone().cache_char_metadata();
+        let z = y.clone();
+        assert!(x.shares_allocation_with(&y));
+        assert_eq!(x.ref_count(), Some(2));
+        assert_eq!(y.ref_count(), Some(2));
+        let size = x.exclusive_heap_size();
+        assert_eq!(x.heap_size(), size / 2);
+        assert_eq!(y.heap_size(), (CACHED_HEADER + size / 2) / 2);
+        assert_eq!(y.heap_size(), z.heap_size());
+    }
+}
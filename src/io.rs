@@ -0,0 +1,151 @@
+use std::{
+    fmt,
+    io::{self, Read},
+};
+
+use crate::FastStr;
+
+impl FastStr {
+    /// Read all bytes from `reader` into a new `FastStr`.
+    ///
+    /// The read buffer becomes the storage of the `FastStr` without being copied again.
+    ///
+    /// Returns an [`io::ErrorKind::InvalidData`] error if the stream is longer than `limit` bytes
+    /// or is not valid UTF-8.
+    pub fn read_from<R: io::Read>(reader: R, limit: usize) -> io::Result<Self> {
+        let mut buf = Vec::new();
+        reader
+            .take((limit as u64).saturating_add(1))
+            .read_to_end(&mut buf)?;
+        if buf.len() > limit {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "stream is longer than the limit",
+            ));
+        }
+        Self::from_vec_u8(buf).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
+
+/// An [`io::Write`] sink that collects bytes into a `FastStr`.
+///
+/// The written bytes are validated incrementally, so a character may be split across several
+/// writes. A write that contains invalid UTF-8 is rejected with [`io::ErrorKind::InvalidData`]
+/// and leaves the writer unchanged.
+#[derive(Clone, Debug, Default)]
+pub struct FastStrWriter {
+    buf: Vec<u8>,
+    valid_up_to: usize,
+}
+
+impl FastStrWriter {
+    /// Create an empty writer.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            buf: Vec::new(),
+            valid_up_to: 0,
+        }
+    }
+
+    /// Create an empty writer with room for `capacity` bytes.
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            buf: Vec::with_capacity(capacity),
+            valid_up_to: 0,
+        }
+    }
+
+    /// Return the number of bytes written so far.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// Return `true` if nothing has been written.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.buf.is_empty()
+    }
+
+    /// Consume the writer and return the written string.
+    ///
+    /// Returns an [`io::ErrorKind::InvalidData`] error if the last character is incomplete.
+    pub fn finish(self) -> io::Result<FastStr> {
+        if self.valid_up_to != self.buf.len() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "stream ended in the middle of a character",
+            ));
+        }
+        // Safety: every byte up to `valid_up_to` has been validated.
+        Ok(unsafe { FastStr::from_vec_u8_unchecked(self.buf) })
+    }
+}
+
+impl io::Write for FastStrWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let old_len = self.buf.len();
+        self.buf.extend_from_slice(buf);
+        match simdutf8::compat::from_utf8(&self.buf[self.valid_up_to..]) {
+            Ok(_) => self.valid_up_to = self.buf.len(),
+            Err(e) if e.error_len().is_none() => self.valid_up_to += e.valid_up_to(),
+            Err(e) => {
+                self.buf.truncate(old_len);
+                return Err(io::Error::new(io::ErrorKind::InvalidData, e));
+            }
+        }
+        Ok(buf.len())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl fmt::Write for FastStrWriter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if self.valid_up_to != self.buf.len() {
+            return Err(fmt::Error);
+        }
+        self.buf.extend_from_slice(s.as_bytes());
+        self.valid_up_to = self.buf.len();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+
+    #[test]
+    fn test_read_from() {
+        let text = "héllo wörld, this is longer than the inline capacity";
+        assert_eq!(FastStr::read_from(text.as_bytes(), 100).unwrap(), text);
+        assert_eq!(
+            FastStr::read_from(text.as_bytes(), text.len()).unwrap(),
+            text
+        );
+        assert!(FastStr::read_from(text.as_bytes(), 10).is_err());
+        assert!(FastStr::read_from(&b"\xff"[..], 10).is_err());
+    }
+
+    #[test]
+    fn test_writer() {
+        let mut w = FastStrWriter::new();
+        let bytes = "héllo".as_bytes();
+        w.write_all(&bytes[..2]).unwrap();
+        w.write_all(&bytes[2..]).unwrap();
+        assert!(w.write_all(b"\xff").is_err());
+        write!(w, ", {}", 42).unwrap();
+        assert_eq!(w.finish().unwrap(), "héllo, 42");
+
+        let mut w = FastStrWriter::new();
+        w.write_all(&bytes[..2]).unwrap();
+        assert!(w.finish().is_err());
+    }
+}
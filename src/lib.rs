@@ -1,7 +1,65 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #![cfg_attr(not(doctest), doc = include_str!("../README.md"))]
 
+#[macro_use]
+mod macros;
+
+mod arena;
+mod ascii;
+#[cfg(feature = "arc-swap")]
+mod atomic;
+mod bounded;
+mod buf;
 mod bytes_ref;
+mod chain;
+mod compact;
+mod encoding;
+mod escape;
+mod heap_size;
+#[cfg(feature = "std")]
+mod io;
+mod local;
+mod meta;
+#[cfg(feature = "unicode-normalization")]
+mod normalize;
+#[cfg(feature = "std")]
+mod os;
+#[cfg(feature = "percent-encoding")]
+mod percent;
+#[cfg(feature = "zeroize")]
+pub mod secret;
+#[cfg(feature = "unicode-segmentation")]
+mod segment;
+mod validated;
+pub mod vec;
+mod weak;
+
+pub use arena::{ArenaUsage, FastStrArena};
+pub use ascii::{AsciiFastStr, NotAsciiError};
+#[cfg(feature = "arc-swap")]
+pub use atomic::AtomicFastStr;
+pub use bounded::{BoundedError, BoundedFastStr};
+pub use buf::FastStrBuf;
+pub use bytes_ref::BytesRef;
+pub use chain::FastStrChain;
+pub use compact::{compact_all, CompactPolicy, VisitFastStr};
+pub use encoding::{Base64Alphabet, DecodeError};
+pub use escape::JsonUnescapeError;
+pub use heap_size::weigher;
+#[cfg(feature = "std")]
+pub use io::FastStrWriter;
+pub use local::LocalFastStr;
+#[cfg(feature = "unicode-normalization")]
+pub use normalize::{Nfc, Nfd, Nfkc, Nfkd, NormalizationForm, NormalizedFastStr};
+#[cfg(feature = "percent-encoding")]
+pub use percent::QueryPairs;
+#[cfg(feature = "zeroize")]
+pub use secret::SecretFastStr;
+#[cfg(feature = "unicode-segmentation")]
+pub use segment::SharedGraphemes;
+pub use validated::{validate, Validated, ValidationError, Validator};
+pub use vec::FastStrVec;
+pub use weak::WeakFastStr;
 
 extern crate alloc;
 
@@ -18,7 +76,16 @@ use core::{
 use bytes::{Bytes, BytesMut};
 use simdutf8::basic::{from_utf8, Utf8Error};
 
-pub use bytes_ref::BytesRef;
+use crate::{meta::CachedRepr, stats::CopyPath};
+
+/// Items used by the expansion of the exported macros.
+#[doc(hidden)]
+pub mod __private {
+    pub use alloc::string::String;
+
+    #[cfg(feature = "serde")]
+    pub use ::serde;
+}
 
 /// `FastStr` is a string type that try to avoid the cost of clone.
 ///
@@ -89,6 +156,7 @@ impl FastStr {
     #[inline]
     pub fn from_arc_str(s: Arc<str>) -> Self {
         if Self::can_inline(&s) {
+            stats::record_inline_promotion(s.len());
             return Self::new(s);
         }
         Self(Repr::from_arc_str(s))
@@ -98,6 +166,7 @@ impl FastStr {
     #[inline]
     pub fn from_string(s: String) -> Self {
         if Self::can_inline(&s) {
+            stats::record_inline_promotion(s.len());
             return Self::new(s);
         }
         Self(Repr::from_string(s))
@@ -107,6 +176,7 @@ impl FastStr {
     #[inline]
     pub fn from_arc_string(s: Arc<String>) -> Self {
         if Self::can_inline(&s) {
+            stats::record_inline_promotion(s.len());
             return Self::new(s.as_str());
         }
         Self(Repr::from_arc_string(s))
@@ -131,6 +201,7 @@ impl FastStr {
     pub unsafe fn from_bytes_unchecked(b: Bytes) -> Self {
         let s = core::str::from_utf8_unchecked(&b);
         if Self::can_inline(s) {
+            stats::record_inline_promotion(s.len());
             return Self::new(s);
         }
         Self(Repr::from_bytes_unchecked(b))
@@ -155,6 +226,7 @@ impl FastStr {
     pub unsafe fn from_bytes_ref_unchecked(b: BytesRef) -> Self {
         let s = core::str::from_utf8_unchecked(&b);
         if Self::can_inline(s) {
+            stats::record_inline_promotion(s.len());
             return Self::new(s);
         }
         Self(Repr::from_bytes_ref_unchecked(b))
@@ -247,6 +319,14 @@ impl FastStr {
         self.0.into_bytes()
     }
 
+    /// Consumes and converts the `FastStr` into a [`bytes::Buf`] reader.
+    ///
+    /// Unlike [`FastStr::into_bytes`], this never copies the string.
+    #[inline]
+    pub fn into_buf(self) -> FastStrBuf {
+        FastStrBuf::new(self)
+    }
+
     /// Return the `FastStr` length.
     #[inline(always)]
     pub fn len(&self) -> usize {
@@ -297,18 +377,73 @@ impl FastStr {
     ///
     /// This is used to free the original memory of the Bytes.
     ///
-    /// This is not stable and may be removed or renamed in the future.
+    /// This is not stable and may be removed or renamed in the future, use
+    /// [`FastStr::compact_if`] instead.
     #[inline]
     #[doc(hidden)]
     pub fn deep_clone_bytes(&self) -> Self {
         Self(self.0.deep_clone_bytes())
     }
 
+    /// Return the kind of the internal representation of the `FastStr`.
+    ///
+    /// This is meant for debugging and diagnostics, the representation chosen by each constructor
+    /// is not guaranteed to be stable.
+    #[inline]
+    pub fn repr_kind(&self) -> ReprKind {
+        self.0.kind()
+    }
+
+    /// Return `true` if the string is stored inline, without any heap allocation.
+    #[inline]
+    pub fn is_inline(&self) -> bool {
+        matches!(self.0, Repr::Inline { .. })
+    }
+
+    /// Return `true` if the string is a `&'static str`.
+    #[inline]
+    pub fn is_static(&self) -> bool {
+        matches!(self.0.uncached(), Repr::StaticStr(_))
+    }
+
+    /// Return `true` if both strings point to the same bytes in memory.
+    #[inline]
+    pub fn ptr_eq(&self, other: &FastStr) -> bool {
+        self.as_ptr() == other.as_ptr() && self.len() == other.len()
+    }
+
+    /// Return `true` if both strings are backed by the same heap allocation.
+    ///
+    /// Two strings created from the same [`Bytes`] are only detected as sharing an allocation if
+    /// their visible ranges of that `Bytes` overlap.
+    pub fn shares_allocation_with(&self, other: &FastStr) -> bool {
+        match (self.0.uncached(), other.0.uncached()) {
+            (Repr::Bytes(a), Repr::Bytes(b)) => {
+                let (a_start, b_start) = (a.data.as_ptr() as usize, b.data.as_ptr() as usize);
+                Arc::ptr_eq(&a.data, &b.data)
+                    || (a_start < b_start + b.data.len() && b_start < a_start + a.data.len())
+            }
+            (Repr::ArcStr(a), Repr::ArcStr(b)) => Arc::ptr_eq(a, b),
+            (Repr::ArcString(a), Repr::ArcString(b)) => Arc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+
+    /// Return the number of strong references to the shared heap allocation, or `None` if the
+    /// string is not backed by one.
+    ///
+    /// For a string returned by [`FastStr::cache_char_metadata`], this counts the references to
+    /// the buffer of the string, and all the clones of the cached string count as one.
+    #[inline]
+    pub fn ref_count(&self) -> Option<usize> {
+        self.0.ref_count()
+    }
+
     fn from_char_iter<I: iter::Iterator<Item = char>>(mut iter: I) -> Self {
         let (min_size, _) = iter.size_hint();
         if min_size > INLINE_CAP {
             let s: String = iter.collect();
-            return Self(Repr::Bytes(Bytes::from(s).into()));
+            return Self(Repr::from_string(s));
         }
         let mut len = 0;
         let mut buf = [0u8; INLINE_CAP];
@@ -320,15 +455,12 @@ impl FastStr {
                 s.push_str(unsafe { core::str::from_utf8_unchecked(&buf[..len]) });
                 s.push(ch);
                 s.extend(iter);
-                return Self(Repr::Bytes(Bytes::from(s).into()));
+                return Self(Repr::from_string(s));
             }
             ch.encode_utf8(&mut buf[len..]);
             len += size;
         }
-        Self(Repr::Inline {
-            len: len as ISize,
-            buf,
-        })
+        Self(Repr::inline(len, buf))
     }
 
     fn can_inline(s: &str) -> bool {
@@ -468,8 +600,18 @@ impl hash::Hash for FastStr {
 }
 
 impl fmt::Debug for FastStr {
+    /// The alternate format (`{:#?}`) also shows the representation and the size of the backing
+    /// buffer.
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if f.alternate() {
+            return f
+                .debug_struct("FastStr")
+                .field("repr", &self.repr_kind())
+                .field("backing_len", &self.0.backing_len())
+                .field("value", &self.as_str())
+                .finish();
+        }
         fmt::Debug::fmt(self.as_str(), f)
     }
 }
@@ -504,15 +646,12 @@ where
             s.push_str(unsafe { core::str::from_utf8_unchecked(&buf[..len]) });
             s.push_str(slice);
             s.extend(iter);
-            return FastStr(Repr::Bytes(Bytes::from(s).into()));
+            return FastStr(Repr::from_string(s));
         }
         buf[len..][..size].copy_from_slice(slice.as_bytes());
         len += size;
     }
-    FastStr(Repr::Inline {
-        len: len as ISize,
-        buf,
-    })
+    FastStr(Repr::inline(len, buf))
 }
 
 impl iter::FromIterator<String> for FastStr {
@@ -603,6 +742,27 @@ impl From<Cow<'static, str>> for FastStr {
 
 const INLINE_CAP: usize = 30;
 
+/// The kind of the internal representation of a [`FastStr`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ReprKind {
+    /// The empty string.
+    Empty,
+    /// A view into a shared [`Bytes`] buffer.
+    Bytes,
+    /// A shared `Arc<str>`.
+    ArcStr,
+    /// A shared `Arc<String>`.
+    ArcString,
+    /// A `&'static str`.
+    Static,
+    /// A string stored inline, without any heap allocation.
+    Inline,
+    /// A shared string that caches its character metadata, created by
+    /// [`FastStr::cache_char_metadata`].
+    Cached,
+}
+
 /// len size of Inline Type
 type ISize = u8;
 
@@ -614,7 +774,12 @@ enum Repr {
     ArcStr(Arc<str>),
     ArcString(Arc<String>),
     StaticStr(&'static str),
-    Inline { len: ISize, buf: [u8; INLINE_CAP] },
+    Inline {
+        len: ISize,
+        buf: [u8; INLINE_CAP],
+    },
+    /// Never wraps an empty, inline or cached repr.
+    Cached(Arc<CachedRepr>),
 }
 
 impl Repr {
@@ -623,8 +788,14 @@ impl Repr {
     where
         T: AsRef<str>,
     {
-        let text = text.as_ref();
+        Self::copy_of(text.as_ref(), CopyPath::New)
+    }
+
+    /// Copy `text` into a new repr, recording the copy under `path` if it cannot be inlined.
+    #[inline]
+    fn copy_of(text: &str, path: CopyPath) -> Self {
         if text.is_empty() {
+            stats::record_created(ReprKind::Empty);
             return Self::Empty;
         }
         {
@@ -635,7 +806,9 @@ impl Repr {
             }
         }
 
-        Self::Bytes(Bytes::copy_from_slice(text.as_bytes()).into())
+        stats::record_copy(path, text.len());
+        // Safety: text is a `str`, thus it's valid utf-8
+        unsafe { Self::from_bytes_unchecked(Bytes::copy_from_slice(text.as_bytes())) }
     }
 
     fn new_inline(s: &str) -> Self {
@@ -652,8 +825,14 @@ impl Repr {
     unsafe fn new_inline_impl(s: &str) -> Self {
         let mut buf = [0u8; INLINE_CAP];
         core::ptr::copy_nonoverlapping(s.as_ptr(), buf.as_mut_ptr(), s.len());
+        Self::inline(s.len(), buf)
+    }
+
+    #[inline]
+    fn inline(len: usize, buf: [u8; INLINE_CAP]) -> Self {
+        stats::record_created(ReprKind::Inline);
         Self::Inline {
-            len: s.len() as ISize,
+            len: len as ISize,
             buf,
         }
     }
@@ -665,6 +844,7 @@ impl Repr {
 
     #[inline]
     fn from_arc_str(s: Arc<str>) -> Self {
+        stats::record_created(ReprKind::ArcStr);
         Self::ArcStr(s)
     }
 
@@ -679,14 +859,17 @@ impl Repr {
     fn from_arc_string(s: Arc<String>) -> Self {
         match Arc::try_unwrap(s) {
             Ok(s) => Self::from_string(s),
-            Err(s) => Self::ArcString(s),
+            Err(s) => {
+                stats::record_created(ReprKind::ArcString);
+                Self::ArcString(s)
+            }
         }
     }
 
     /// Safety: the caller must guarantee that the bytes `v` are valid UTF-8.
     #[inline]
     unsafe fn from_bytes_unchecked(bytes: Bytes) -> Self {
-        Self::Bytes(bytes.into())
+        Self::from_bytes_ref_unchecked(bytes.into())
     }
 
     /// # Safety
@@ -694,9 +877,55 @@ impl Repr {
     /// `bytes` must represent a valid UTF-8 byte sequence.
     #[inline]
     unsafe fn from_bytes_ref_unchecked(bytes: BytesRef) -> Self {
+        stats::record_created(ReprKind::Bytes);
         Self::Bytes(bytes)
     }
 
+    #[inline]
+    fn kind(&self) -> ReprKind {
+        match self {
+            Self::Empty => ReprKind::Empty,
+            Self::Bytes(_) => ReprKind::Bytes,
+            Self::ArcStr(_) => ReprKind::ArcStr,
+            Self::ArcString(_) => ReprKind::ArcString,
+            Self::StaticStr(_) => ReprKind::Static,
+            Self::Inline { .. } => ReprKind::Inline,
+            Self::Cached(_) => ReprKind::Cached,
+        }
+    }
+
+    /// Return the repr holding the bytes, looking through the metadata cache.
+    #[inline]
+    fn uncached(&self) -> &Self {
+        match self {
+            Self::Cached(c) => &c.repr,
+            repr => repr,
+        }
+    }
+
+    /// Return the number of strong references to the buffer holding the bytes.
+    #[inline]
+    fn ref_count(&self) -> Option<usize> {
+        match self {
+            Self::Bytes(b) => Some(Arc::strong_count(&b.data)),
+            Self::ArcStr(s) => Some(Arc::strong_count(s)),
+            Self::ArcString(s) => Some(Arc::strong_count(s)),
+            Self::Cached(c) => c.repr.ref_count(),
+            Self::Empty | Self::StaticStr(_) | Self::Inline { .. } => None,
+        }
+    }
+
+    /// Return the size of the buffer kept alive by this repr.
+    #[inline]
+    fn backing_len(&self) -> usize {
+        match self {
+            Self::Bytes(bytes) => bytes.data.len().max(bytes.len),
+            Self::ArcString(arc_string) => arc_string.capacity(),
+            Self::Cached(c) => c.repr.backing_len(),
+            Self::Empty | Self::ArcStr(_) | Self::StaticStr(_) | Self::Inline { .. } => self.len(),
+        }
+    }
+
     #[inline]
     fn len(&self) -> usize {
         match self {
@@ -706,6 +935,7 @@ impl Repr {
             Self::ArcString(arc_string) => arc_string.len(),
             Self::StaticStr(s) => s.len(),
             Self::Inline { len, .. } => *len as usize,
+            Self::Cached(c) => c.repr.len(),
         }
     }
 
@@ -718,6 +948,7 @@ impl Repr {
             Self::ArcString(arc_string) => arc_string.is_empty(),
             Self::StaticStr(s) => s.is_empty(),
             Self::Inline { len, .. } => *len == 0,
+            Self::Cached(c) => c.repr.is_empty(),
         }
     }
 
@@ -733,6 +964,7 @@ impl Repr {
             Self::Inline { len, buf } => unsafe {
                 core::str::from_utf8_unchecked(&buf[..*len as usize])
             },
+            Self::Cached(c) => c.repr.as_str(),
         }
     }
 
@@ -753,6 +985,10 @@ impl Repr {
             Self::Inline { len, buf } => unsafe {
                 String::from_utf8_unchecked(buf[..len as usize].to_vec())
             },
+            Self::Cached(c) => match Arc::try_unwrap(c) {
+                Ok(c) => c.repr.into_string(),
+                Err(c) => c.repr.as_str().to_string(),
+            },
         }
     }
 
@@ -761,12 +997,25 @@ impl Repr {
         match self {
             Self::Empty => Bytes::new(),
             Self::Bytes(bytes) => bytes.into(),
-            Self::ArcStr(arc_str) => Bytes::from(arc_str.as_bytes().to_vec()),
+            Self::ArcStr(arc_str) => {
+                stats::record_copy(CopyPath::IntoBytes, arc_str.len());
+                Bytes::from(arc_str.as_bytes().to_vec())
+            }
             Self::ArcString(arc_string) => {
-                Bytes::from(Arc::try_unwrap(arc_string).unwrap_or_else(|arc| (*arc).clone()))
+                Bytes::from(Arc::try_unwrap(arc_string).unwrap_or_else(|arc| {
+                    stats::record_copy(CopyPath::IntoBytes, arc.len());
+                    (*arc).clone()
+                }))
             }
             Self::StaticStr(s) => Bytes::from_static(s.as_bytes()),
-            Self::Inline { len, buf } => Bytes::from(buf[..len as usize].to_vec()),
+            Self::Inline { len, buf } => {
+                stats::record_copy(CopyPath::IntoBytes, len as usize);
+                Bytes::from(buf[..len as usize].to_vec())
+            }
+            Self::Cached(c) => match Arc::try_unwrap(c) {
+                Ok(c) => c.repr.into_bytes(),
+                Err(c) => c.repr.clone().into_bytes(),
+            },
         }
     }
 
@@ -775,7 +1024,9 @@ impl Repr {
         match self {
             Self::Empty => Self::Empty,
             // Safety: this is guaranteed by the user when creating the `FastStr`.
-            Self::Bytes(bytes) => unsafe { Self::new(core::str::from_utf8_unchecked(bytes)) },
+            Self::Bytes(bytes) => unsafe {
+                Self::copy_of(core::str::from_utf8_unchecked(bytes), CopyPath::DeepClone)
+            },
             Self::ArcStr(arc_str) => Self::ArcStr(Arc::clone(arc_str)),
             Self::ArcString(arc_string) => Self::ArcString(Arc::clone(arc_string)),
             Self::StaticStr(s) => Self::StaticStr(s),
@@ -783,12 +1034,17 @@ impl Repr {
                 len: *len,
                 buf: *buf,
             },
+            Self::Cached(c) => match c.repr {
+                Self::Bytes(_) => c.repr.deep_clone_bytes(),
+                _ => Self::Cached(Arc::clone(c)),
+            },
         }
     }
 
     #[inline]
     fn slice_ref(&self, subset: &[u8]) -> Self {
         if subset.is_empty() {
+            stats::record_created(ReprKind::Empty);
             return Self::Empty;
         }
         let bytes_p = self.as_ref().as_ptr() as usize;
@@ -815,24 +1071,23 @@ impl Repr {
         let sub_offset = sub_p - bytes_p;
         match self {
             Repr::Empty => panic!("invalid slice ref, self is empty but subset is not"),
-            Repr::Bytes(b) => Self::Bytes(unsafe { b.slice_ref(subset) }),
-            Repr::ArcStr(s) => Self::Bytes(
-                Bytes::copy_from_slice(s[sub_offset..sub_offset + sub_len].as_bytes()).into(),
-            ),
-            Repr::ArcString(s) => Self::Bytes(
-                Bytes::copy_from_slice(s[sub_offset..sub_offset + sub_len].as_bytes()).into(),
-            ),
-            Repr::StaticStr(s) => Self::StaticStr(unsafe {
-                core::str::from_utf8_unchecked(&s.as_bytes()[sub_offset..sub_offset + sub_len])
+            Repr::Bytes(b) => unsafe { Self::from_bytes_ref_unchecked(b.slice_ref(subset)) },
+            Repr::ArcStr(_) | Repr::ArcString(_) => {
+                stats::record_copy(CopyPath::SliceRef, sub_len);
+                unsafe { Self::from_bytes_unchecked(Bytes::copy_from_slice(subset)) }
+            }
+            Repr::StaticStr(s) => {
+                stats::record_created(ReprKind::Static);
+                Self::StaticStr(unsafe {
+                    core::str::from_utf8_unchecked(&s.as_bytes()[sub_offset..sub_offset + sub_len])
+                })
+            }
+            Repr::Inline { len: _, buf } => Self::inline(sub_len, {
+                let mut new_buf = [0; INLINE_CAP];
+                new_buf[..sub_len].copy_from_slice(&buf[sub_offset..sub_offset + sub_len]);
+                new_buf
             }),
-            Repr::Inline { len: _, buf } => Self::Inline {
-                len: sub_len as ISize,
-                buf: {
-                    let mut new_buf = [0; INLINE_CAP];
-                    new_buf[..sub_len].copy_from_slice(&buf[sub_offset..sub_offset + sub_len]);
-                    new_buf
-                },
-            },
+            Repr::Cached(c) => c.repr.slice_ref(subset),
         }
     }
 }
@@ -847,6 +1102,7 @@ impl AsRef<[u8]> for Repr {
             Self::ArcString(arc_string) => arc_string.as_bytes(),
             Self::StaticStr(s) => s.as_bytes(),
             Self::Inline { len, buf } => &buf[..*len as usize],
+            Self::Cached(c) => c.repr.as_ref(),
         }
     }
 }
@@ -870,3 +1126,16 @@ pub mod sea_orm;
 
 #[cfg(feature = "sqlx-postgres")]
 pub mod sqlx_postgres;
+
+#[cfg(feature = "mmap")]
+mod mmap;
+
+#[cfg(feature = "strict-zero-copy")]
+mod strict;
+#[cfg(feature = "strict-zero-copy")]
+pub use strict::deny_copies;
+
+#[cfg(feature = "stats")]
+pub mod stats;
+#[cfg(not(feature = "stats"))]
+mod stats;
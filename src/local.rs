@@ -0,0 +1,659 @@
+use alloc::{borrow::Cow, rc::Rc, string::String, vec::Vec};
+use core::{
+    borrow::Borrow, cmp::Ordering, convert::Infallible, fmt, hash, iter, ops::Deref, slice,
+    str::FromStr,
+};
+
+use bytes::{Buf, Bytes, BytesMut};
+use simdutf8::basic::{from_utf8, Utf8Error};
+
+use crate::{FastStr, ISize, Repr, INLINE_CAP};
+
+/// A single-threaded variant of [`FastStr`] that shares heap buffers with [`Rc`] instead of
+/// [`Arc`](alloc::sync::Arc).
+///
+/// Cloning a heap-allocated `LocalFastStr` does not touch any atomic, which makes it cheaper on
+/// thread-per-core runtimes. Short strings are inlined and static strings are borrowed, exactly
+/// like `FastStr`.
+///
+/// `LocalFastStr` is `!Send`. Convert it with [`LocalFastStr::into_faststr`] when the value
+/// needs to cross threads.
+#[derive(Clone)]
+pub struct LocalFastStr(LocalRepr);
+
+#[cfg(all(test, target_pointer_width = "64"))]
+mod size_asserts {
+    static_assertions::assert_eq_size!(super::LocalFastStr, [u8; 32]); // 32 bytes
+}
+
+#[derive(Clone)]
+enum LocalRepr {
+    Empty,
+    Bytes(LocalBytesRef),
+    RcStr(Rc<str>),
+    RcString(Rc<String>),
+    StaticStr(&'static str),
+    Inline { len: ISize, buf: [u8; INLINE_CAP] },
+}
+
+/// A view into a `Bytes` shared with [`Rc`], like [`BytesRef`](crate::BytesRef) does with
+/// `Arc`.
+#[derive(Clone)]
+struct LocalBytesRef {
+    ptr: *const u8,
+    len: usize,
+    data: Rc<Bytes>,
+}
+
+impl From<Bytes> for LocalBytesRef {
+    #[inline]
+    fn from(data: Bytes) -> Self {
+        Self {
+            ptr: data.as_ptr(),
+            len: data.len(),
+            data: Rc::new(data),
+        }
+    }
+}
+
+impl LocalBytesRef {
+    #[inline]
+    fn as_bytes(&self) -> &[u8] {
+        // Safety: `ptr` and `len` always describe a part of `data`, which is kept alive.
+        unsafe { slice::from_raw_parts(self.ptr, self.len) }
+    }
+
+    /// Return a view of `subset`, sharing the buffer.
+    ///
+    /// # Safety
+    ///
+    /// `subset` must be contained in the bytes visible through `self`.
+    #[inline]
+    unsafe fn slice_ref(&self, subset: &[u8]) -> Self {
+        Self {
+            ptr: subset.as_ptr(),
+            len: subset.len(),
+            data: self.data.clone(),
+        }
+    }
+
+    /// Return the viewed bytes as a `Bytes`, which only copies the handle, not the bytes.
+    #[inline]
+    fn into_bytes(self) -> Bytes {
+        let offset = self.ptr as usize - self.data.as_ptr() as usize;
+        let mut data = Rc::try_unwrap(self.data).unwrap_or_else(|rc| (*rc).clone());
+        data.truncate(offset + self.len);
+        data.advance(offset);
+        data
+    }
+}
+
+impl LocalFastStr {
+    /// Create a new `LocalFastStr` by copying `text`.
+    ///
+    /// For small strings (up to 30 bytes), this avoids heap allocation, and copies on stack.
+    #[inline]
+    pub fn new<T>(text: T) -> Self
+    where
+        T: AsRef<str>,
+    {
+        let text = text.as_ref();
+        if text.is_empty() {
+            return Self::empty();
+        }
+        Self::inline(text).unwrap_or_else(|| {
+            Self(LocalRepr::Bytes(
+                Bytes::copy_from_slice(text.as_bytes()).into(),
+            ))
+        })
+    }
+
+    /// Create a new `LocalFastStr` from a byte slice `v`, returning an error if the bytes are not
+    /// valid UTF-8.
+    #[inline]
+    pub fn new_u8_slice(v: &[u8]) -> Result<Self, Utf8Error> {
+        let s = from_utf8(v)?;
+        Ok(Self::new(s))
+    }
+
+    /// Create a new `LocalFastStr` from a byte slice `v`.
+    ///
+    /// # Safety
+    ///
+    /// `v` must be valid UTF-8.
+    #[inline]
+    pub unsafe fn new_u8_slice_unchecked(v: &[u8]) -> Self {
+        Self::new(core::str::from_utf8_unchecked(v))
+    }
+
+    /// Create an empty `LocalFastStr`.
+    #[inline]
+    pub const fn empty() -> Self {
+        Self(LocalRepr::Empty)
+    }
+
+    /// Create a new `LocalFastStr` from a `&'static str`.
+    #[inline]
+    pub const fn from_static_str(s: &'static str) -> Self {
+        Self(LocalRepr::StaticStr(s))
+    }
+
+    /// Create a new `LocalFastStr` from a `String` without copying it.
+    #[inline]
+    pub fn from_string(s: String) -> Self {
+        Self::inline(&s)
+            .unwrap_or_else(|| Self(LocalRepr::Bytes(Bytes::from(s.into_bytes()).into())))
+    }
+
+    /// Create a new `LocalFastStr` from a `Bytes` without copying it, returning an error if the
+    /// bytes are not valid UTF-8.
+    #[inline]
+    pub fn from_bytes(b: Bytes) -> Result<Self, Utf8Error> {
+        from_utf8(&b)?;
+        // Safety: we have checked b is utf-8 valid
+        Ok(unsafe { Self::from_bytes_unchecked(b) })
+    }
+
+    /// Create a new `LocalFastStr` from a `Bytes` without copying it.
+    ///
+    /// # Safety
+    ///
+    /// `b` must be valid UTF-8.
+    #[inline]
+    pub unsafe fn from_bytes_unchecked(b: Bytes) -> Self {
+        Self::inline(core::str::from_utf8_unchecked(&b))
+            .unwrap_or_else(|| Self(LocalRepr::Bytes(b.into())))
+    }
+
+    /// Create a new `LocalFastStr` from a `BytesMut` without copying it, returning an error if
+    /// the bytes are not valid UTF-8.
+    #[inline]
+    pub fn from_bytes_mut(b: BytesMut) -> Result<Self, Utf8Error> {
+        Self::from_bytes(b.freeze())
+    }
+
+    /// Create a new `LocalFastStr` from a `BytesMut` without copying it.
+    ///
+    /// # Safety
+    ///
+    /// `b` must be valid UTF-8.
+    #[inline]
+    pub unsafe fn from_bytes_mut_unchecked(b: BytesMut) -> Self {
+        Self::from_bytes_unchecked(b.freeze())
+    }
+
+    /// Create a new `LocalFastStr` from a `Vec<u8>` without copying it, returning an error if
+    /// the bytes are not valid UTF-8.
+    #[inline]
+    pub fn from_vec_u8(v: Vec<u8>) -> Result<Self, Utf8Error> {
+        Self::from_bytes(v.into())
+    }
+
+    /// Create a new `LocalFastStr` from a `Vec<u8>` without copying it.
+    ///
+    /// # Safety
+    ///
+    /// `v` must be valid UTF-8.
+    #[inline]
+    pub unsafe fn from_vec_u8_unchecked(v: Vec<u8>) -> Self {
+        Self::from_bytes_unchecked(v.into())
+    }
+
+    /// Create a new `LocalFastStr` from an `Rc<str>`.
+    #[inline]
+    pub fn from_rc_str(s: Rc<str>) -> Self {
+        Self::inline(&s).unwrap_or(Self(LocalRepr::RcStr(s)))
+    }
+
+    /// Create a new `LocalFastStr` from an `Rc<String>`.
+    ///
+    /// The `String` is moved out of the `Rc` if it is not shared.
+    #[inline]
+    pub fn from_rc_string(s: Rc<String>) -> Self {
+        if let Some(inline) = Self::inline(&s) {
+            return inline;
+        }
+        match Rc::try_unwrap(s) {
+            Ok(s) => Self::from_string(s),
+            Err(s) => Self(LocalRepr::RcString(s)),
+        }
+    }
+
+    /// Return the string slice.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        match &self.0 {
+            LocalRepr::Empty => "",
+            // Safety: the bytes are checked to be utf-8 by the constructors.
+            LocalRepr::Bytes(b) => unsafe { core::str::from_utf8_unchecked(b.as_bytes()) },
+            LocalRepr::RcStr(s) => s,
+            LocalRepr::RcString(s) => s,
+            LocalRepr::StaticStr(s) => s,
+            // Safety: the inline buffer is only filled from string slices.
+            LocalRepr::Inline { len, buf } => unsafe {
+                core::str::from_utf8_unchecked(&buf[..*len as usize])
+            },
+        }
+    }
+
+    /// Return the length of the string in bytes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.as_str().len()
+    }
+
+    /// Return `true` if the string is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Return a new `LocalFastStr` that represents a subset of the current string.
+    ///
+    /// Note: If the subset is small enough, it will be inlined. Other subsets share the buffer,
+    /// except for strings created from an `Rc<str>` or a shared `Rc<String>`, whose subsets are
+    /// copied.
+    #[inline]
+    pub fn slice_ref(&self, subset: &str) -> Self {
+        if subset.is_empty() {
+            return Self::empty();
+        }
+        if let Some(inline) = Self::inline(subset) {
+            return inline;
+        }
+        let bytes_p = self.as_ptr() as usize;
+        let sub_p = subset.as_ptr() as usize;
+        assert!(
+            sub_p >= bytes_p && sub_p + subset.len() <= bytes_p + self.len(),
+            "subset is out of bounds: self = ({:p}, {}), subset = ({:p}, {})",
+            self.as_ptr(),
+            self.len(),
+            subset.as_ptr(),
+            subset.len(),
+        );
+        match &self.0 {
+            // Safety: we have checked that subset is in bounds.
+            LocalRepr::Bytes(b) => {
+                Self(LocalRepr::Bytes(unsafe { b.slice_ref(subset.as_bytes()) }))
+            }
+            LocalRepr::StaticStr(s) => {
+                Self::from_static_str(&s[sub_p - bytes_p..sub_p - bytes_p + subset.len()])
+            }
+            _ => Self::new(subset),
+        }
+    }
+
+    /// If the string is backed by `Bytes`, return a copy that no longer keeps the buffer alive.
+    /// Otherwise, return a clone of the string, which has no cost.
+    ///
+    /// Like [`FastStr::deep_clone_bytes`], this is not stable and may be removed or renamed in the
+    /// future.
+    #[inline]
+    #[doc(hidden)]
+    pub fn deep_clone_bytes(&self) -> Self {
+        match &self.0 {
+            LocalRepr::Bytes(_) => Self::new(self.as_str()),
+            _ => self.clone(),
+        }
+    }
+
+    /// Consume and convert into a `Bytes`.
+    ///
+    /// Strings backed by `Bytes` only copy the handle and static strings are borrowed, other
+    /// strings are converted through [`LocalFastStr::into_string`].
+    #[inline]
+    pub fn into_bytes(self) -> Bytes {
+        match self.0 {
+            LocalRepr::Bytes(b) => b.into_bytes(),
+            LocalRepr::StaticStr(s) => Bytes::from_static(s.as_bytes()),
+            _ => Bytes::from(self.into_string()),
+        }
+    }
+
+    /// Consume and convert into a `String`.
+    ///
+    /// The buffer is reused if the string owns it and does not share it, and copied otherwise.
+    #[inline]
+    pub fn into_string(self) -> String {
+        match self.0 {
+            LocalRepr::Bytes(b) => {
+                // Safety: the bytes are checked to be utf-8 by the constructors.
+                unsafe { String::from_utf8_unchecked(b.into_bytes().into()) }
+            }
+            LocalRepr::RcString(s) => Rc::try_unwrap(s).unwrap_or_else(|s| (*s).clone()),
+            _ => String::from(self.as_str()),
+        }
+    }
+
+    /// Convert into a [`FastStr`] that can be sent to other threads.
+    ///
+    /// Empty, static and inline strings are converted without copying, and so are strings backed
+    /// by `Bytes` and an unshared `String`. Other heap-allocated strings are copied.
+    #[inline]
+    pub fn into_faststr(self) -> FastStr {
+        match self.0 {
+            LocalRepr::Empty => FastStr::empty(),
+            // Safety: the bytes are checked to be utf-8 by the constructors.
+            LocalRepr::Bytes(b) => unsafe { FastStr::from_bytes_unchecked(b.into_bytes()) },
+            LocalRepr::StaticStr(s) => FastStr::from_static_str(s),
+            LocalRepr::RcString(s) => match Rc::try_unwrap(s) {
+                Ok(s) => FastStr::from_string(s),
+                Err(s) => FastStr::new(s.as_str()),
+            },
+            LocalRepr::RcStr(s) => FastStr::new(s),
+            LocalRepr::Inline { .. } => FastStr::new(self.as_str()),
+        }
+    }
+
+    #[inline]
+    fn inline(s: &str) -> Option<Self> {
+        if s.len() > INLINE_CAP {
+            return None;
+        }
+        let mut buf = [0; INLINE_CAP];
+        buf[..s.len()].copy_from_slice(s.as_bytes());
+        Some(Self(LocalRepr::Inline {
+            len: s.len() as ISize,
+            buf,
+        }))
+    }
+}
+
+impl From<FastStr> for LocalFastStr {
+    /// Static strings are borrowed, short strings are inlined, and the `Bytes` of other strings
+    /// is shared without copying when [`FastStr::into_bytes`] does not copy.
+    #[inline]
+    fn from(s: FastStr) -> Self {
+        match s.0 {
+            Repr::StaticStr(s) => Self::from_static_str(s),
+            _ if s.len() <= INLINE_CAP => Self::new(s),
+            // Safety: a `FastStr` is utf-8.
+            _ => unsafe { Self::from_bytes_unchecked(s.into_bytes()) },
+        }
+    }
+}
+
+impl From<LocalFastStr> for FastStr {
+    #[inline]
+    fn from(s: LocalFastStr) -> Self {
+        s.into_faststr()
+    }
+}
+
+impl From<String> for LocalFastStr {
+    #[inline]
+    fn from(s: String) -> Self {
+        Self::from_string(s)
+    }
+}
+
+impl From<LocalFastStr> for String {
+    #[inline]
+    fn from(s: LocalFastStr) -> Self {
+        s.into_string()
+    }
+}
+
+impl From<LocalFastStr> for Bytes {
+    #[inline]
+    fn from(s: LocalFastStr) -> Self {
+        s.into_bytes()
+    }
+}
+
+impl From<Cow<'static, str>> for LocalFastStr {
+    #[inline]
+    fn from(s: Cow<'static, str>) -> Self {
+        match s {
+            Cow::Borrowed(s) => Self::from_static_str(s),
+            Cow::Owned(s) => Self::from_string(s),
+        }
+    }
+}
+
+impl From<&'static str> for LocalFastStr {
+    #[inline]
+    fn from(s: &'static str) -> Self {
+        Self::from_static_str(s)
+    }
+}
+
+impl From<Rc<str>> for LocalFastStr {
+    #[inline]
+    fn from(s: Rc<str>) -> Self {
+        Self::from_rc_str(s)
+    }
+}
+
+impl From<Rc<String>> for LocalFastStr {
+    #[inline]
+    fn from(s: Rc<String>) -> Self {
+        Self::from_rc_string(s)
+    }
+}
+
+impl FromStr for LocalFastStr {
+    type Err = Infallible;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self::new(s))
+    }
+}
+
+/// Collect like [`FastStr`] does, inline when short enough, then move the buffer into a
+/// `LocalFastStr`.
+macro_rules! impl_from_iterator {
+    ($($item:ty),+) => {
+        $(
+            impl<'a> iter::FromIterator<$item> for LocalFastStr {
+                #[inline]
+                fn from_iter<I: iter::IntoIterator<Item = $item>>(iter: I) -> Self {
+                    FastStr::from_iter(iter).into()
+                }
+            }
+        )+
+    };
+}
+
+impl_from_iterator!(char, String, &'a String, &'a str);
+
+impl Default for LocalFastStr {
+    #[inline]
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+impl Deref for LocalFastStr {
+    type Target = str;
+
+    #[inline]
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for LocalFastStr {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<[u8]> for LocalFastStr {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl Borrow<str> for LocalFastStr {
+    #[inline]
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl PartialEq for LocalFastStr {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for LocalFastStr {}
+
+impl PartialEq<str> for LocalFastStr {
+    #[inline]
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl<'a> PartialEq<&'a str> for LocalFastStr {
+    #[inline]
+    fn eq(&self, other: &&'a str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl PartialEq<FastStr> for LocalFastStr {
+    #[inline]
+    fn eq(&self, other: &FastStr) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl PartialEq<LocalFastStr> for FastStr {
+    #[inline]
+    fn eq(&self, other: &LocalFastStr) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl PartialEq<String> for LocalFastStr {
+    #[inline]
+    fn eq(&self, other: &String) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<LocalFastStr> for String {
+    #[inline]
+    fn eq(&self, other: &LocalFastStr) -> bool {
+        self == other.as_str()
+    }
+}
+
+impl Ord for LocalFastStr {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl PartialOrd for LocalFastStr {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl hash::Hash for LocalFastStr {
+    #[inline]
+    fn hash<H: hash::Hasher>(&self, hasher: &mut H) {
+        self.as_str().hash(hasher)
+    }
+}
+
+impl fmt::Debug for LocalFastStr {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl fmt::Display for LocalFastStr {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self.as_str(), f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_local() {
+        let long = "a string that is too long to be inlined";
+        let s = LocalFastStr::from_string(String::from(long));
+        let clone = s.clone();
+        assert_eq!(clone.slice_ref(&clone[2..]), long[2..]);
+        assert_eq!(s.as_ptr(), clone.as_ptr());
+
+        // The buffer of a `String` is shared, but a shared `Rc<String>` is copied.
+        let moved = s.into_faststr();
+        assert_eq!(moved.as_ptr(), clone.as_ptr());
+        assert_eq!(FastStr::from(clone), moved);
+        let rc = Rc::new(String::from(long));
+        let copied = LocalFastStr::from_rc_string(rc.clone()).into_faststr();
+        assert_ne!(copied.as_ptr(), rc.as_ptr());
+        assert_eq!(copied, moved);
+
+        let s = LocalFastStr::from(FastStr::from_static_str(long));
+        assert!(s.into_faststr().is_static());
+        assert_eq!(LocalFastStr::new("short").into_faststr(), "short");
+    }
+
+    #[test]
+    fn test_local_shares_bytes() {
+        let long = "a string that is too long to be inlined";
+        let b = Bytes::from(long);
+        let s = LocalFastStr::from_bytes(b.clone()).unwrap();
+        assert_eq!(s.as_ptr(), b.as_ptr());
+        let sub = s.slice_ref(&s[1..]);
+        assert_eq!(sub.as_ptr(), s[1..].as_ptr());
+        assert_eq!(FastStr::from(sub.clone()).as_ptr(), sub.as_ptr());
+        assert_eq!(Bytes::from(sub.clone()), &long.as_bytes()[1..]);
+
+        let v = Vec::from(long);
+        let ptr = v.as_ptr();
+        let s = LocalFastStr::from_vec_u8(v).unwrap();
+        let string = s.into_string();
+        assert_eq!(string.as_ptr(), ptr);
+        assert!(LocalFastStr::from_bytes_mut(BytesMut::from(&b"\xff"[..])).is_err());
+        assert!(LocalFastStr::new_u8_slice(b"\xff").is_err());
+
+        let f = FastStr::from_string(String::from(long));
+        assert_eq!(LocalFastStr::from(f.clone()).as_ptr(), f.as_ptr());
+
+        let s: LocalFastStr = "parsed".parse().unwrap();
+        assert_eq!(s, String::from("parsed"));
+        assert_eq!(["a", "b", "c"].into_iter().collect::<LocalFastStr>(), "abc");
+        assert_eq!(LocalFastStr::from(Cow::Borrowed(long)), long);
+    }
+
+    #[test]
+    fn test_local_into_bytes() {
+        let long = "a string that is too long to be inlined";
+        let b = Bytes::from(long);
+        let s = LocalFastStr::from_bytes(b.clone()).unwrap();
+        let sub = s.slice_ref(&s[1..]);
+        let bytes = sub.clone().into_bytes();
+        assert_eq!(bytes, &long.as_bytes()[1..]);
+        assert_eq!(bytes.as_ptr(), sub.as_ptr());
+        assert_eq!(
+            LocalFastStr::from_static_str(long).into_bytes().as_ptr(),
+            long.as_ptr()
+        );
+        assert_eq!(LocalFastStr::new("short").into_bytes(), "short");
+
+        let copy = sub.deep_clone_bytes();
+        assert_eq!(copy, sub);
+        assert_ne!(copy.as_ptr(), sub.as_ptr());
+        let rc = LocalFastStr::from_rc_str(Rc::from(long));
+        assert_eq!(rc.deep_clone_bytes().as_ptr(), rc.as_ptr());
+    }
+}
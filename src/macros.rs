@@ -0,0 +1,124 @@
+/// Implement the accessors and the string traits shared by the newtypes around `FastStr`.
+///
+/// `impl_faststr_newtype!([generics] Type, field)` adds `as_str`, `as_faststr` and
+/// `into_inner`, `From<Type> for FastStr`, `Deref<Target = str>`, `AsRef<str>`, `Borrow<str>`,
+/// comparisons with `Type`, `str`, `&str` and `FastStr`, and `Hash`, `Debug` and `Display`, all
+/// forwarded to the `FastStr` in `field`.
+macro_rules! impl_faststr_newtype {
+    ([$($gen:tt)*] $ty:ty, $field:tt) => {
+        impl<$($gen)*> $ty {
+            /// Return the string slice.
+            #[inline(always)]
+            pub fn as_str(&self) -> &str {
+                self.$field.as_str()
+            }
+
+            /// Return the underlying `FastStr`.
+            #[inline(always)]
+            pub fn as_faststr(&self) -> &$crate::FastStr {
+                &self.$field
+            }
+
+            /// Consume and return the underlying `FastStr`.
+            #[inline(always)]
+            pub fn into_inner(self) -> $crate::FastStr {
+                self.$field
+            }
+        }
+
+        impl<$($gen)*> From<$ty> for $crate::FastStr {
+            #[inline]
+            fn from(s: $ty) -> Self {
+                s.$field
+            }
+        }
+
+        impl<$($gen)*> core::ops::Deref for $ty {
+            type Target = str;
+
+            #[inline]
+            fn deref(&self) -> &str {
+                self.as_str()
+            }
+        }
+
+        impl<$($gen)*> AsRef<str> for $ty {
+            #[inline]
+            fn as_ref(&self) -> &str {
+                self.as_str()
+            }
+        }
+
+        impl<$($gen)*> core::borrow::Borrow<str> for $ty {
+            #[inline]
+            fn borrow(&self) -> &str {
+                self.as_str()
+            }
+        }
+
+        impl<$($gen)*> PartialEq for $ty {
+            #[inline]
+            fn eq(&self, other: &Self) -> bool {
+                self.$field == other.$field
+            }
+        }
+
+        impl<$($gen)*> Eq for $ty {}
+
+        impl<$($gen)*> PartialEq<str> for $ty {
+            #[inline]
+            fn eq(&self, other: &str) -> bool {
+                self.as_str() == other
+            }
+        }
+
+        impl<$($gen)*> PartialEq<&str> for $ty {
+            #[inline]
+            fn eq(&self, other: &&str) -> bool {
+                self.as_str() == *other
+            }
+        }
+
+        impl<$($gen)*> PartialEq<$crate::FastStr> for $ty {
+            #[inline]
+            fn eq(&self, other: &$crate::FastStr) -> bool {
+                &self.$field == other
+            }
+        }
+
+        impl<$($gen)*> Ord for $ty {
+            #[inline]
+            fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+                self.$field.cmp(&other.$field)
+            }
+        }
+
+        impl<$($gen)*> PartialOrd for $ty {
+            #[inline]
+            fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl<$($gen)*> core::hash::Hash for $ty {
+            #[inline]
+            fn hash<H: core::hash::Hasher>(&self, hasher: &mut H) {
+                self.$field.hash(hasher)
+            }
+        }
+
+        impl<$($gen)*> core::fmt::Debug for $ty {
+            #[inline]
+            fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                core::fmt::Debug::fmt(&self.$field, f)
+            }
+        }
+
+        impl<$($gen)*> core::fmt::Display for $ty {
+            #[inline]
+            fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                core::fmt::Display::fmt(&self.$field, f)
+            }
+        }
+    };
+}
@@ -0,0 +1,177 @@
+use alloc::{sync::Arc, vec::Vec};
+use core::{
+    char::DecodeUtf16Error,
+    sync::atomic::{AtomicUsize, Ordering::Relaxed},
+};
+
+use crate::{stats, FastStr, Repr, ReprKind};
+
+/// Marks a count that has not been computed yet. No string is `usize::MAX` bytes long.
+const UNKNOWN: usize = usize::MAX;
+
+/// A shared repr with its character counts, computed on first use.
+pub(crate) struct CachedRepr {
+    pub(crate) repr: Repr,
+    char_len: AtomicUsize,
+    utf16_len: AtomicUsize,
+}
+
+impl CachedRepr {
+    /// Return the number of chars and of UTF-16 code units, computing both on the first call.
+    ///
+    /// Racing threads may both compute the counts, they store the same values.
+    #[inline]
+    fn counts(&self) -> (usize, usize) {
+        let (char_len, utf16_len) = (self.char_len.load(Relaxed), self.utf16_len.load(Relaxed));
+        if char_len != UNKNOWN && utf16_len != UNKNOWN {
+            return (char_len, utf16_len);
+        }
+        let (char_len, utf16_len) = count(self.repr.as_ref());
+        self.char_len.store(char_len, Relaxed);
+        self.utf16_len.store(utf16_len, Relaxed);
+        (char_len, utf16_len)
+    }
+}
+
+impl FastStr {
+    /// Return a `FastStr` that remembers its char count, UTF-16 length and whether it is ASCII
+    /// once they have been computed, for strings that are queried repeatedly.
+    ///
+    /// The buffer of the string is shared, not copied. Empty and inline strings are returned
+    /// as is, since they are short enough to scan every time.
+    ///
+    /// ```
+    /// use faststr::FastStr;
+    ///
+    /// let s = FastStr::from_string("naïve ".repeat(8)).cache_char_metadata();
+    /// assert_eq!(s.char_len(), 48);
+    /// assert_eq!(s.utf16_len(), 48);
+    /// assert!(!s.is_ascii_cached());
+    /// ```
+    pub fn cache_char_metadata(self) -> Self {
+        match self.0 {
+            Repr::Empty | Repr::Inline { .. } | Repr::Cached(_) => self,
+            repr => {
+                stats::record_created(ReprKind::Cached);
+                Self(Repr::Cached(Arc::new(CachedRepr {
+                    repr,
+                    char_len: AtomicUsize::new(UNKNOWN),
+                    utf16_len: AtomicUsize::new(UNKNOWN),
+                })))
+            }
+        }
+    }
+
+    /// Return the number of chars in the string, like `self.chars().count()`.
+    ///
+    /// The count is remembered by strings returned by [`FastStr::cache_char_metadata`], and
+    /// computed on every call otherwise.
+    #[inline]
+    pub fn char_len(&self) -> usize {
+        match &self.0 {
+            Repr::Cached(c) => c.counts().0,
+            repr => count(repr.as_ref()).0,
+        }
+    }
+
+    /// Return the length of the string in UTF-16 code units, like
+    /// `self.encode_utf16().count()`.
+    ///
+    /// The length is remembered by strings returned by [`FastStr::cache_char_metadata`], and
+    /// computed on every call otherwise.
+    #[inline]
+    pub fn utf16_len(&self) -> usize {
+        match &self.0 {
+            Repr::Cached(c) => c.counts().1,
+            repr => count(repr.as_ref()).1,
+        }
+    }
+
+    /// Return `true` if the string is ASCII, like `self.is_ascii()`.
+    ///
+    /// The answer is remembered by strings returned by [`FastStr::cache_char_metadata`], and
+    /// computed on every call otherwise.
+    #[inline]
+    pub fn is_ascii_cached(&self) -> bool {
+        match &self.0 {
+            Repr::Cached(c) => c.counts().0 == self.len(),
+            repr => repr.as_ref().is_ascii(),
+        }
+    }
+
+    /// Decode UTF-16 code units into a new `FastStr`, inline if the result is short enough.
+    ///
+    /// Returns an error on the first unpaired surrogate.
+    #[inline]
+    pub fn from_utf16(v: &[u16]) -> Result<Self, DecodeUtf16Error> {
+        char::decode_utf16(v.iter().copied()).collect()
+    }
+
+    /// Encode the string as UTF-16 code units, in a buffer allocated once with
+    /// [`FastStr::utf16_len`] units.
+    #[inline]
+    pub fn encode_utf16_faststr(&self) -> Vec<u16> {
+        let mut v = Vec::with_capacity(self.utf16_len());
+        v.extend(self.encode_utf16());
+        v
+    }
+}
+
+/// Return the number of chars and of UTF-16 code units in the UTF-8 `bytes`.
+///
+/// Every byte that is not a continuation byte starts a char, and chars of four bytes take two
+/// UTF-16 code units.
+#[inline]
+fn count(bytes: &[u8]) -> (usize, usize) {
+    let (chars, wide) = bytes.iter().fold((0, 0), |(chars, wide), &b| {
+        (
+            chars + ((b as i8) >= -0x40) as usize,
+            wide + (b >= 0xf0) as usize,
+        )
+    });
+    (chars, chars + wide)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::String;
+
+    use super::*;
+
+    #[test]
+    fn test_char_metadata() {
+        let text = "ASCII, ünïcödé and 𝄞 in a string too long to be inlined";
+        let s = FastStr::from_string(String::from(text)).cache_char_metadata();
+        assert_eq!(s.repr_kind(), ReprKind::Cached);
+        for s in [s.clone(), FastStr::new(text), FastStr::new("𝄞é")] {
+            assert_eq!(s.char_len(), s.chars().count());
+            assert_eq!(s.utf16_len(), s.encode_utf16().count());
+            assert_eq!(s.is_ascii_cached(), s.is_ascii());
+            // The second call reads the cache.
+            assert_eq!(s.utf16_len(), s.encode_utf16().count());
+        }
+
+        let ascii = FastStr::from_static_str("a static string too long to be inlined");
+        let cached = ascii.clone().cache_char_metadata();
+        assert!(cached.is_ascii_cached());
+        assert_eq!(cached.as_ptr(), ascii.as_ptr());
+        assert_eq!(cached.slice_ref(&cached[2..]), ascii[2..]);
+        assert_eq!(
+            FastStr::new("inline").cache_char_metadata().repr_kind(),
+            ReprKind::Inline
+        );
+    }
+
+    #[test]
+    fn test_utf16() {
+        for text in ["inline 𝄞", "a string with 𝄞 that is too long to be inlined"] {
+            let units = FastStr::new(text).encode_utf16_faststr();
+            assert_eq!(units.len(), units.capacity());
+            let s = FastStr::from_utf16(&units).unwrap();
+            assert_eq!(s, text);
+            assert_eq!(s.is_inline(), text.len() <= 30);
+        }
+        let err = FastStr::from_utf16(&[0x61, 0xd834, 0x62]).unwrap_err();
+        assert_eq!(err.unpaired_surrogate(), 0xd834);
+    }
+}
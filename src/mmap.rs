@@ -0,0 +1,70 @@
+use std::{fs::File, io, path::Path};
+
+use bytes::Bytes;
+use memmap2::Mmap;
+
+use crate::FastStr;
+
+impl FastStr {
+    /// Map the file at `path` into memory and return its content as a `FastStr`.
+    ///
+    /// The content is validated as UTF-8 once and is never copied to the heap. The mapping is
+    /// owned by the returned `FastStr` and every string sliced from it, and is unmapped when the
+    /// last of them is dropped.
+    ///
+    /// Returns an [`io::ErrorKind::InvalidData`] error if the file is not valid UTF-8.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified or truncated while it is mapped, see [`Mmap::map`].
+    pub unsafe fn map_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let file = File::open(path)?;
+        if file.metadata()?.len() == 0 {
+            return Ok(Self::empty());
+        }
+        let mmap = Mmap::map(&file)?;
+        simdutf8::basic::from_utf8(&mmap)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        // Safety: we have checked the mapping is utf-8 valid
+        Ok(Self::from_bytes_unchecked(Bytes::from_owner(mmap)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf};
+
+    use super::*;
+
+    fn temp_file(name: &str, content: &[u8]) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("faststr-{}-{}", std::process::id(), name));
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_map_file() {
+        let text = "key = value\n".repeat(100);
+        let path = temp_file("map", text.as_bytes());
+        let s = unsafe { FastStr::map_file(&path) }.unwrap();
+        assert_eq!(s, text);
+
+        let sub = s.slice_ref(&s[12..]);
+        let ptr = sub.as_ptr();
+        drop(s);
+        assert_eq!(sub, text[12..]);
+        assert_eq!(sub.into_bytes().as_ptr(), ptr);
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_map_file_invalid() {
+        let empty = temp_file("empty", b"");
+        assert_eq!(unsafe { FastStr::map_file(&empty) }.unwrap(), "");
+        let invalid = temp_file("invalid", b"\xff\xfe");
+        let err = unsafe { FastStr::map_file(&invalid) }.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        fs::remove_file(empty).unwrap();
+        fs::remove_file(invalid).unwrap();
+    }
+}
@@ -0,0 +1,178 @@
+use core::{marker::PhantomData, str::Chars};
+
+use unicode_normalization::{
+    is_nfc_quick, is_nfd_quick, is_nfkc_quick, is_nfkd_quick, IsNormalized, UnicodeNormalization,
+};
+
+use crate::FastStr;
+
+impl FastStr {
+    /// Return the string in Normalization Form C.
+    ///
+    /// The Unicode quick check runs first, and the string is cloned without copying if it is
+    /// already normalized.
+    #[inline]
+    pub fn nfc(&self) -> Self {
+        Nfc::normalize(self)
+    }
+
+    /// Return the string in Normalization Form D.
+    ///
+    /// The Unicode quick check runs first, and the string is cloned without copying if it is
+    /// already normalized.
+    #[inline]
+    pub fn nfd(&self) -> Self {
+        Nfd::normalize(self)
+    }
+
+    /// Return the string in Normalization Form KC.
+    ///
+    /// The Unicode quick check runs first, and the string is cloned without copying if it is
+    /// already normalized.
+    #[inline]
+    pub fn nfkc(&self) -> Self {
+        Nfkc::normalize(self)
+    }
+
+    /// Return the string in Normalization Form KD.
+    ///
+    /// The Unicode quick check runs first, and the string is cloned without copying if it is
+    /// already normalized.
+    #[inline]
+    pub fn nfkd(&self) -> Self {
+        Nfkd::normalize(self)
+    }
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// A Unicode normalization form, used as the parameter of [`NormalizedFastStr`].
+pub trait NormalizationForm: sealed::Sealed {
+    /// Return `s` in this form.
+    fn normalize(s: &FastStr) -> FastStr;
+}
+
+macro_rules! normalization_form {
+    ($(#[$meta:meta])* $form:ident, $quick:ident, $normalize:ident) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        pub struct $form;
+
+        impl sealed::Sealed for $form {}
+
+        impl NormalizationForm for $form {
+            #[inline]
+            fn normalize(s: &FastStr) -> FastStr {
+                normalize(s, |c| $quick(c), |s| s.$normalize().collect())
+            }
+        }
+    };
+}
+
+normalization_form!(
+    /// Normalization Form C, canonical composition.
+    Nfc,
+    is_nfc_quick,
+    nfc
+);
+normalization_form!(
+    /// Normalization Form D, canonical decomposition.
+    Nfd,
+    is_nfd_quick,
+    nfd
+);
+normalization_form!(
+    /// Normalization Form KC, compatibility composition.
+    Nfkc,
+    is_nfkc_quick,
+    nfkc
+);
+normalization_form!(
+    /// Normalization Form KD, compatibility decomposition.
+    Nfkd,
+    is_nfkd_quick,
+    nfkd
+);
+
+#[inline]
+fn normalize(
+    s: &FastStr,
+    quick: fn(Chars) -> IsNormalized,
+    normalize: fn(&str) -> FastStr,
+) -> FastStr {
+    if quick(s.chars()) == IsNormalized::Yes {
+        return s.clone();
+    }
+    let normalized = normalize(s);
+    // The quick check may answer "maybe" for strings that are normalized.
+    if normalized == *s {
+        return s.clone();
+    }
+    normalized
+}
+
+/// A `FastStr` guaranteed to be in the normalization form `F`.
+///
+/// Two `NormalizedFastStr` of the same form compare equal exactly when they are canonically (or
+/// compatibly, for the K forms) equivalent.
+///
+/// ```
+/// use faststr::{FastStr, Nfc, NormalizedFastStr};
+///
+/// let composed = NormalizedFastStr::<Nfc>::new(FastStr::new("caf\u{e9}"));
+/// let decomposed = NormalizedFastStr::<Nfc>::new(FastStr::new("cafe\u{301}"));
+/// assert_eq!(composed, decomposed);
+/// ```
+pub struct NormalizedFastStr<F> {
+    inner: FastStr,
+    _form: PhantomData<F>,
+}
+
+impl<F: NormalizationForm> NormalizedFastStr<F> {
+    /// Normalize `s` into the form `F`.
+    #[inline]
+    pub fn new(s: FastStr) -> Self {
+        Self {
+            inner: F::normalize(&s),
+            _form: PhantomData,
+        }
+    }
+}
+
+impl<F: NormalizationForm> From<FastStr> for NormalizedFastStr<F> {
+    #[inline]
+    fn from(s: FastStr) -> Self {
+        Self::new(s)
+    }
+}
+
+impl_faststr_newtype!([F] NormalizedFastStr<F>, inner);
+
+impl<F> Clone for NormalizedFastStr<F> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            _form: PhantomData,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        let nfc = FastStr::from_string("Am\u{e9}lie, a name that is too long to inline".into());
+        assert_eq!(nfc.nfc().as_ptr(), nfc.as_ptr());
+        let nfd = nfc.nfd();
+        assert_eq!(nfd.len(), nfc.len() + 1);
+        assert_eq!(nfd.nfc(), nfc);
+        assert_eq!(FastStr::new("\u{fb01}").nfkc(), "fi");
+        assert_eq!(FastStr::new("\u{fb01}").nfc(), "\u{fb01}");
+        assert_eq!(FastStr::new("\u{1e9b}\u{323}").nfkd(), "s\u{323}\u{307}");
+    }
+}
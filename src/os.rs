@@ -0,0 +1,108 @@
+use std::{
+    ffi::{CString, IntoStringError, NulError, OsStr, OsString},
+    path::{Path, PathBuf},
+};
+
+use crate::FastStr;
+
+impl FastStr {
+    /// Copy the string into a `CString`.
+    ///
+    /// Returns an error if the string contains a nul byte.
+    #[inline]
+    pub fn to_cstring(&self) -> Result<CString, NulError> {
+        CString::new(self.as_bytes())
+    }
+}
+
+impl AsRef<OsStr> for FastStr {
+    #[inline]
+    fn as_ref(&self) -> &OsStr {
+        OsStr::new(self.as_str())
+    }
+}
+
+impl AsRef<Path> for FastStr {
+    #[inline]
+    fn as_ref(&self) -> &Path {
+        Path::new(self.as_str())
+    }
+}
+
+impl TryFrom<OsString> for FastStr {
+    type Error = OsString;
+
+    /// Convert an `OsString` that is valid UTF-8 without copying it, or give it back.
+    #[inline]
+    fn try_from(s: OsString) -> Result<Self, Self::Error> {
+        s.into_string().map(Self::from_string)
+    }
+}
+
+impl TryFrom<PathBuf> for FastStr {
+    type Error = PathBuf;
+
+    /// Convert a `PathBuf` that is valid UTF-8 without copying it, or give it back.
+    #[inline]
+    fn try_from(p: PathBuf) -> Result<Self, Self::Error> {
+        Self::try_from(p.into_os_string()).map_err(PathBuf::from)
+    }
+}
+
+impl TryFrom<CString> for FastStr {
+    type Error = IntoStringError;
+
+    /// Convert a `CString` that is valid UTF-8 without copying it. The `CString` can be
+    /// recovered from the error with [`IntoStringError::into_cstring`].
+    #[inline]
+    fn try_from(s: CString) -> Result<Self, Self::Error> {
+        s.into_string().map(Self::from_string)
+    }
+}
+
+impl From<FastStr> for OsString {
+    #[inline]
+    fn from(s: FastStr) -> Self {
+        String::from(s).into()
+    }
+}
+
+impl From<FastStr> for PathBuf {
+    #[inline]
+    fn from(s: FastStr) -> Self {
+        String::from(s).into()
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::{fs, os::unix::ffi::OsStringExt};
+
+    use super::*;
+
+    #[test]
+    fn test_os_conversions() {
+        let path = OsString::from("/a/path/that/is/too/long/to/be/inlined");
+        let ptr = path.as_encoded_bytes().as_ptr();
+        let s = FastStr::try_from(path).unwrap();
+        assert_eq!(s.as_ptr(), ptr);
+        assert_eq!(AsRef::<Path>::as_ref(&s).file_name().unwrap(), "inlined");
+        assert_eq!(PathBuf::from(s.clone()), Path::new(s.as_str()));
+
+        let bad = OsString::from_vec(vec![b'/', 0xff]);
+        assert_eq!(FastStr::try_from(bad.clone()), Err(bad));
+        assert!(FastStr::try_from(PathBuf::from("Cargo.toml")).is_ok());
+        assert!(fs::metadata(FastStr::from_static_str("Cargo.toml")).is_ok());
+
+        let c = FastStr::new("nul-free").to_cstring().unwrap();
+        assert_eq!(FastStr::try_from(c).unwrap(), "nul-free");
+        assert!(FastStr::new("a\0b").to_cstring().is_err());
+        let invalid = CString::new(vec![0xff]).unwrap();
+        assert_eq!(
+            FastStr::try_from(invalid.clone())
+                .unwrap_err()
+                .into_cstring(),
+            invalid
+        );
+    }
+}
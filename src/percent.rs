@@ -0,0 +1,141 @@
+use alloc::{borrow::Cow, string::String, vec::Vec};
+use core::iter::FusedIterator;
+
+use percent_encoding::{percent_decode, utf8_percent_encode, AsciiSet};
+use simdutf8::basic::Utf8Error;
+
+use crate::FastStr;
+
+impl FastStr {
+    /// Decode the `%XX` escapes in the string.
+    ///
+    /// The string is cloned without copying if it has no escape. Returns an error if the
+    /// decoded bytes are not valid UTF-8.
+    #[inline]
+    pub fn percent_decode(&self) -> Result<Self, Utf8Error> {
+        match percent_decode(self.as_bytes()).into() {
+            Cow::Borrowed(_) => Ok(self.clone()),
+            Cow::Owned(v) => Self::from_vec_u8(v),
+        }
+    }
+
+    /// Percent-encode the bytes of the string that are in `set`, and every non-ASCII byte.
+    ///
+    /// The sets are defined by the [`percent_encoding`] crate. The string is cloned without
+    /// copying if nothing needs to be encoded.
+    #[inline]
+    pub fn percent_encode(&self, set: &'static AsciiSet) -> Self {
+        match utf8_percent_encode(self, set).into() {
+            Cow::Borrowed(_) => self.clone(),
+            Cow::Owned(s) => Self::from_string(s),
+        }
+    }
+
+    /// Parse the string as `application/x-www-form-urlencoded` data, such as a URL query.
+    ///
+    /// Names and values that contain no `+` or `%` share the buffer of the string, others are
+    /// decoded into new strings, replacing invalid UTF-8 with `U+FFFD`.
+    ///
+    /// ```
+    /// use faststr::FastStr;
+    ///
+    /// let query = FastStr::new("name=faststr&tags=a+b&empty");
+    /// let mut pairs = query.parse_query();
+    /// assert_eq!(pairs.next(), Some(("name".into(), "faststr".into())));
+    /// assert_eq!(pairs.next(), Some(("tags".into(), "a b".into())));
+    /// assert_eq!(pairs.next(), Some(("empty".into(), "".into())));
+    /// assert_eq!(pairs.next(), None);
+    /// ```
+    #[inline]
+    pub fn parse_query(&self) -> QueryPairs<'_> {
+        QueryPairs {
+            input: self,
+            rest: self.as_str(),
+        }
+    }
+
+    fn decode_form_component(&self, part: &str) -> Self {
+        if !part.bytes().any(|b| b == b'+' || b == b'%') {
+            return self.slice_ref(part);
+        }
+        let replaced: Vec<u8> = part
+            .bytes()
+            .map(|b| if b == b'+' { b' ' } else { b })
+            .collect();
+        let decoded: Cow<[u8]> = percent_decode(&replaced).into();
+        match String::from_utf8_lossy(&decoded) {
+            Cow::Borrowed(s) => Self::new(s),
+            Cow::Owned(s) => Self::from_string(s),
+        }
+    }
+}
+
+/// An iterator over the name and value pairs of a query, created by [`FastStr::parse_query`].
+#[derive(Clone, Debug)]
+pub struct QueryPairs<'a> {
+    input: &'a FastStr,
+    rest: &'a str,
+}
+
+impl Iterator for QueryPairs<'_> {
+    type Item = (FastStr, FastStr);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.rest.is_empty() {
+                return None;
+            }
+            let (pair, rest) = self.rest.split_once('&').unwrap_or((self.rest, ""));
+            self.rest = rest;
+            if pair.is_empty() {
+                continue;
+            }
+            let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+            return Some((
+                self.input.decode_form_component(name),
+                self.input.decode_form_component(value),
+            ));
+        }
+    }
+}
+
+impl FusedIterator for QueryPairs<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use percent_encoding::NON_ALPHANUMERIC;
+
+    use super::*;
+
+    #[test]
+    fn test_percent() {
+        let clean = FastStr::from_string("a-path-that-is-too-long-to-be-inlined".into());
+        assert_eq!(clean.percent_decode().unwrap().as_ptr(), clean.as_ptr());
+        assert_eq!(
+            FastStr::new("caf%C3%A9%20au%20lait")
+                .percent_decode()
+                .unwrap(),
+            "café au lait"
+        );
+        assert!(FastStr::new("%FF").percent_decode().is_err());
+
+        let plain = FastStr::new("abc123");
+        assert_eq!(plain.percent_encode(NON_ALPHANUMERIC), plain);
+        assert_eq!(
+            FastStr::new("café au lait").percent_encode(NON_ALPHANUMERIC),
+            "caf%C3%A9%20au%20lait"
+        );
+    }
+
+    #[test]
+    fn test_parse_query() {
+        let query = FastStr::from_string(
+            "key=a-value-that-is-too-long-to-be-inlined&&q=caf%C3%A9+au+lait&bad=%FF".into(),
+        );
+        let pairs: Vec<_> = query.parse_query().collect();
+        assert_eq!(pairs.len(), 3);
+        assert!(query.shares_allocation_with(&pairs[0].1));
+        assert_eq!(pairs[1].1, "café au lait");
+        assert_eq!(pairs[2].1, "\u{FFFD}");
+    }
+}
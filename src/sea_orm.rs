@@ -1,6 +1,6 @@
 use sea_orm::prelude::StringLen;
 
-use crate::FastStr;
+use crate::{BoundedFastStr, FastStr};
 
 impl std::convert::From<FastStr> for sea_orm::Value {
     fn from(source: FastStr) -> Self {
@@ -51,6 +51,53 @@ impl sea_orm::sea_query::ValueType for FastStr {
     }
 }
 
+impl<const MAX: usize> std::convert::From<BoundedFastStr<MAX>> for sea_orm::Value {
+    fn from(source: BoundedFastStr<MAX>) -> Self {
+        FastStr::from(source).into()
+    }
+}
+
+impl<const MAX: usize> sea_orm::TryGetable for BoundedFastStr<MAX> {
+    fn try_get_by<I: sea_orm::ColIdx>(
+        res: &sea_orm::QueryResult,
+        idx: I,
+    ) -> Result<Self, sea_orm::TryGetError> {
+        let val: String = String::try_get_by(res, idx)?;
+        BoundedFastStr::from_string(val)
+            .map_err(|e| sea_orm::TryGetError::DbErr(sea_orm::DbErr::Type(e.to_string())))
+    }
+}
+
+impl<const MAX: usize> sea_orm::sea_query::Nullable for BoundedFastStr<MAX> {
+    fn null() -> sea_orm::Value {
+        sea_orm::Value::String(None)
+    }
+}
+
+impl<const MAX: usize> sea_orm::sea_query::ValueType for BoundedFastStr<MAX> {
+    fn try_from(v: sea_orm::Value) -> Result<Self, sea_orm::sea_query::ValueTypeErr> {
+        match v {
+            sea_orm::Value::String(Some(x)) => {
+                BoundedFastStr::from_string(*x).map_err(|_| sea_orm::sea_query::ValueTypeErr)
+            }
+            _ => Err(sea_orm::sea_query::ValueTypeErr),
+        }
+    }
+
+    fn type_name() -> String {
+        format!("BoundedFastStr<{MAX}>")
+    }
+
+    fn array_type() -> sea_orm::sea_query::ArrayType {
+        sea_orm::sea_query::ArrayType::String
+    }
+
+    /// `VARCHAR(MAX)`.
+    fn column_type() -> sea_orm::sea_query::ColumnType {
+        sea_orm::sea_query::ColumnType::String(StringLen::N(MAX as u32))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use sea_orm::{
@@ -199,4 +246,16 @@ mod tests {
             Ok(FastStr::from_static_str("1234567890"))
         );
     }
+
+    #[test]
+    fn test_bounded_column_type() {
+        use sea_orm::sea_query::{ColumnType, ValueType};
+
+        assert_eq!(
+            <BoundedFastStr<64> as ValueType>::column_type(),
+            ColumnType::String(StringLen::N(64))
+        );
+        let too_long = sea_orm::Value::from(FastStr::new("x".repeat(65)));
+        assert!(<BoundedFastStr<64> as ValueType>::try_from(too_long).is_err());
+    }
 }
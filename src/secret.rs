@@ -0,0 +1,180 @@
+//! A `FastStr` wrapper for passwords, tokens and other secrets, enabled by the `zeroize`
+//! feature.
+
+use alloc::sync::Arc;
+use core::{fmt, hint, mem};
+
+use zeroize::Zeroize;
+
+use crate::{FastStr, Repr};
+
+/// The placeholder written by the `Debug`, `Display` and `Serialize` implementations of
+/// [`SecretFastStr`].
+pub const REDACTED: &str = "[REDACTED]";
+
+/// A `FastStr` holding a secret.
+///
+/// - `Debug`, `Display` and `Serialize` write [`REDACTED`] instead of the value, read it with
+///   [`SecretFastStr::expose`].
+/// - Equality is checked in constant time for strings of the same length.
+/// - On drop, the bytes are overwritten with zeros if this is the last reference to them. Inline
+///   strings are always wiped, static strings never are.
+///
+/// Clones share the same buffer, which is only wiped when the last of them is dropped. Copies
+/// made through [`SecretFastStr::expose`] are not tracked.
+#[derive(Clone, Default)]
+pub struct SecretFastStr(FastStr);
+
+impl SecretFastStr {
+    /// Wrap `s` as a secret.
+    #[inline]
+    pub fn new(s: FastStr) -> Self {
+        Self(s)
+    }
+
+    /// Return the secret value.
+    #[inline]
+    pub fn expose(&self) -> &FastStr {
+        &self.0
+    }
+
+    /// Return the length of the secret in bytes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Return `true` if the secret is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl From<FastStr> for SecretFastStr {
+    #[inline]
+    fn from(s: FastStr) -> Self {
+        Self::new(s)
+    }
+}
+
+impl Drop for SecretFastStr {
+    fn drop(&mut self) {
+        wipe(&mut self.0 .0);
+    }
+}
+
+/// Zero the bytes of `repr`, unless they are shared with another reference.
+fn wipe(repr: &mut Repr) {
+    match repr {
+        Repr::Empty | Repr::StaticStr(_) => {}
+        Repr::Inline { buf, .. } => buf.zeroize(),
+        Repr::Bytes(b) => {
+            if let Some(data) = Arc::get_mut(&mut b.data) {
+                // Zeros are valid UTF-8, and no other reference to the bytes is left.
+                if let Ok(mut data) = mem::take(data).try_into_mut() {
+                    data.zeroize();
+                }
+            }
+        }
+        Repr::ArcStr(s) => {
+            if let Some(s) = Arc::get_mut(s) {
+                // Safety: zeros are valid UTF-8.
+                unsafe { s.as_bytes_mut() }.zeroize();
+            }
+        }
+        Repr::ArcString(s) => {
+            if let Some(s) = Arc::get_mut(s) {
+                s.zeroize();
+            }
+        }
+        Repr::Cached(c) => {
+            if let Some(c) = Arc::get_mut(c) {
+                wipe(&mut c.repr);
+            }
+        }
+    }
+}
+
+impl PartialEq for SecretFastStr {
+    /// Compare the secrets in constant time, only their lengths may leak.
+    fn eq(&self, other: &Self) -> bool {
+        let (a, b) = (self.0.as_bytes(), other.0.as_bytes());
+        if a.len() != b.len() {
+            return false;
+        }
+        let diff = a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y));
+        hint::black_box(diff) == 0
+    }
+}
+
+impl Eq for SecretFastStr {}
+
+impl fmt::Debug for SecretFastStr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("SecretFastStr").field(&REDACTED).finish()
+    }
+}
+
+impl fmt::Display for SecretFastStr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+/// Serialize the value of a [`SecretFastStr`] instead of [`REDACTED`].
+///
+/// Use it on a field with `#[serde(serialize_with = "faststr::secret::serialize_exposed")]`.
+///
+/// ```
+/// # use faststr::{secret::serialize_exposed, FastStr, SecretFastStr};
+/// let token = SecretFastStr::new(FastStr::from_static_str("s3cr3t"));
+/// let mut json = Vec::new();
+/// serialize_exposed(&token, &mut serde_json::Serializer::new(&mut json)).unwrap();
+/// assert_eq!(json, br#""s3cr3t""#);
+/// ```
+#[cfg(feature = "serde")]
+pub fn serialize_exposed<S>(secret: &SecretFastStr, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serde::Serialize::serialize(secret.expose(), serializer)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{format, string::String};
+
+    use bytes::Bytes;
+
+    use super::*;
+
+    #[test]
+    fn test_secret() {
+        let token = SecretFastStr::new(FastStr::from_string("s3cr3t".repeat(10)));
+        assert_eq!(format!("{token}"), REDACTED);
+        assert_eq!(format!("{token:?}"), "SecretFastStr(\"[REDACTED]\")");
+        assert_eq!(token.expose(), &"s3cr3t".repeat(10));
+        assert_eq!(
+            token,
+            SecretFastStr::new(FastStr::from_arc_str("s3cr3t".repeat(10).into()))
+        );
+        assert_ne!(token, SecretFastStr::new(FastStr::new("s3cr3t")));
+    }
+
+    #[test]
+    fn test_shared_not_wiped() {
+        let text = "a secret that is too long to be inlined";
+        let secret =
+            SecretFastStr::new(FastStr::from_bytes(Bytes::from(String::from(text))).unwrap());
+        let shared = secret.clone();
+        drop(secret);
+        assert_eq!(shared.expose(), text);
+
+        let owner = Arc::new(String::from(text));
+        drop(SecretFastStr::new(FastStr::from_arc_string(Arc::clone(
+            &owner,
+        ))));
+        assert_eq!(owner.as_str(), text);
+    }
+}
@@ -0,0 +1,160 @@
+use alloc::string::String;
+use core::iter::FusedIterator;
+
+use unicode_segmentation::{Graphemes, UnicodeSegmentation};
+use unicode_width::UnicodeWidthStr;
+
+use crate::{FastStr, Repr, INLINE_CAP};
+
+impl FastStr {
+    /// Return the first `n` extended grapheme clusters of the string.
+    ///
+    /// The string is cloned without copying if it has at most `n` clusters, so that a character
+    /// is never split, even when it is made of several code points.
+    #[inline]
+    pub fn truncate_graphemes(&self, n: usize) -> Self {
+        match self.grapheme_indices(true).nth(n) {
+            Some((end, _)) => self.slice_ref(&self[..end]),
+            None => self.clone(),
+        }
+    }
+
+    /// Truncate the string to at most `width` columns of [display width], and append `ellipsis`
+    /// if anything was cut. The ellipsis counts toward `width`, and is itself truncated if it is
+    /// wider than `width`.
+    ///
+    /// The string is cloned without copying if it already fits. Grapheme clusters are never
+    /// split.
+    ///
+    /// ```
+    /// use faststr::FastStr;
+    ///
+    /// let s = FastStr::new("日本語のテキスト");
+    /// assert_eq!(s.truncate_display_width(9, "…"), "日本語の…");
+    /// assert_eq!(s.truncate_display_width(16, "…"), s);
+    /// ```
+    ///
+    /// [display width]: FastStr::display_width
+    pub fn truncate_display_width(&self, width: usize, ellipsis: &str) -> Self {
+        if self.display_width() <= width {
+            return self.clone();
+        }
+        let Some(budget) = width.checked_sub(ellipsis.width()) else {
+            return Self::new(ellipsis).truncate_display_width(width, "");
+        };
+        let mut used = 0;
+        let mut end = 0;
+        for (i, g) in self.grapheme_indices(true) {
+            used += g.width();
+            if used > budget {
+                break;
+            }
+            end = i + g.len();
+        }
+        if ellipsis.is_empty() {
+            return self.slice_ref(&self[..end]);
+        }
+        let len = end + ellipsis.len();
+        if len <= INLINE_CAP {
+            let mut buf = [0; INLINE_CAP];
+            buf[..end].copy_from_slice(&self.as_bytes()[..end]);
+            buf[end..len].copy_from_slice(ellipsis.as_bytes());
+            return Self(Repr::inline(len, buf));
+        }
+        let mut s = String::with_capacity(len);
+        s.push_str(&self[..end]);
+        s.push_str(ellipsis);
+        Self(Repr::from_string(s))
+    }
+
+    /// Return an iterator over the extended grapheme clusters of the string.
+    ///
+    /// Each cluster is a `FastStr` sharing the buffer of the string, or inlined if it is short
+    /// enough.
+    #[inline]
+    pub fn graphemes_shared(&self) -> SharedGraphemes<'_> {
+        SharedGraphemes {
+            input: self,
+            inner: self.graphemes(true),
+        }
+    }
+
+    /// Return the number of columns the string takes in a terminal, as defined by
+    /// [Unicode Standard Annex #11](https://www.unicode.org/reports/tr11/).
+    #[inline]
+    pub fn display_width(&self) -> usize {
+        self.as_str().width()
+    }
+}
+
+/// An iterator over the grapheme clusters of a `FastStr`, created by
+/// [`FastStr::graphemes_shared`].
+#[derive(Clone, Debug)]
+pub struct SharedGraphemes<'a> {
+    input: &'a FastStr,
+    inner: Graphemes<'a>,
+}
+
+impl Iterator for SharedGraphemes<'_> {
+    type Item = FastStr;
+
+    #[inline]
+    fn next(&mut self) -> Option<FastStr> {
+        self.inner.next().map(|g| self.input.slice_ref(g))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for SharedGraphemes<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<FastStr> {
+        self.inner.next_back().map(|g| self.input.slice_ref(g))
+    }
+}
+
+impl FusedIterator for SharedGraphemes<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::*;
+
+    #[test]
+    fn test_truncate_graphemes() {
+        let s = FastStr::new("e\u{301}👩‍👩‍👧🇨🇳x");
+        assert_eq!(s.truncate_graphemes(2), "e\u{301}👩‍👩‍👧");
+        assert_eq!(s.truncate_graphemes(0), "");
+        assert_eq!(s.truncate_graphemes(4), s);
+
+        let long = FastStr::from_string("a string that is too long to be inlined".into());
+        assert_eq!(long.truncate_graphemes(100).as_ptr(), long.as_ptr());
+        assert!(long.truncate_graphemes(35).shares_allocation_with(&long));
+    }
+
+    #[test]
+    fn test_display_width() {
+        let s = FastStr::new("ab日本👍");
+        assert_eq!(s.display_width(), 8);
+        assert_eq!(s.truncate_display_width(5, "..."), "ab...");
+        assert_eq!(s.truncate_display_width(7, ""), "ab日本");
+        assert_eq!(s.truncate_display_width(5, ""), "ab日");
+        assert_eq!(s.truncate_display_width(3, "..."), "...");
+        assert_eq!(s.truncate_display_width(2, "..."), "..");
+        assert_eq!(s.truncate_display_width(0, "..."), "");
+        assert_eq!(s.truncate_display_width(8, "..."), s);
+    }
+
+    #[test]
+    fn test_graphemes_shared() {
+        let s = FastStr::from_string("🇨🇳 a string that is too long to be inlined".into());
+        let clusters: Vec<_> = s.graphemes_shared().collect();
+        assert_eq!(clusters[0], "🇨🇳");
+        assert_eq!(clusters.len(), 41);
+        assert_eq!(s.graphemes_shared().next_back().unwrap(), "d");
+    }
+}
@@ -3,91 +3,280 @@ use core::fmt;
 
 #[cfg(not(feature = "serde-unsafe"))]
 use serde::de::Unexpected;
-use serde::de::{Deserializer, Error, Visitor};
+use serde::{
+    de::{DeserializeSeed, Deserializer, Error, SeqAccess, Visitor},
+    ser::SerializeSeq,
+};
 
-use crate::FastStr;
+use crate::{BoundedFastStr, FastStr, FastStrVec, LocalFastStr, Validated, Validator};
 
 // https://github.com/serde-rs/serde/blob/629802f2abfd1a54a6072992888fea7ca5bc209f/serde/src/private/de.rs#L56-L125
+struct FastStrVisitor;
+
+impl<'a> Visitor<'a> for FastStrVisitor {
+    type Value = FastStr;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a string")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        Ok(FastStr::new(v))
+    }
+
+    fn visit_borrowed_str<E>(self, v: &'a str) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        Ok(FastStr::new(v))
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        Ok(FastStr::from(v))
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        #[cfg(feature = "serde-unsafe")]
+        {
+            Ok(unsafe { FastStr::new_u8_slice_unchecked(v) })
+        }
+        #[cfg(not(feature = "serde-unsafe"))]
+        match FastStr::new_u8_slice(v) {
+            Ok(s) => Ok(s),
+            Err(_) => Err(Error::invalid_value(Unexpected::Bytes(v), &self)),
+        }
+    }
+
+    fn visit_borrowed_bytes<E>(self, v: &'a [u8]) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        #[cfg(feature = "serde-unsafe")]
+        {
+            Ok(unsafe { FastStr::new_u8_slice_unchecked(v) })
+        }
+        #[cfg(not(feature = "serde-unsafe"))]
+        match FastStr::new_u8_slice(v) {
+            Ok(s) => Ok(s),
+            Err(_) => Err(Error::invalid_value(Unexpected::Bytes(v), &self)),
+        }
+    }
+
+    fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        #[cfg(not(feature = "serde-unsafe"))]
+        simdutf8::basic::from_utf8(&v)
+            .map_err(|_| Error::invalid_value(Unexpected::Bytes(&v), &self))?;
+        // Safety: we have checked that v is valid utf-8
+        Ok(unsafe { FastStr::from_vec_u8_unchecked(v) })
+    }
+}
+
 fn fast_str<'de: 'a, 'a, D>(deserializer: D) -> Result<FastStr, D::Error>
 where
     D: Deserializer<'de>,
 {
-    struct FastStrVisitor;
+    deserializer.deserialize_string(FastStrVisitor)
+}
 
-    impl<'a> Visitor<'a> for FastStrVisitor {
-        type Value = FastStr;
+impl serde::Serialize for FastStr {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.as_str().serialize(serializer)
+    }
+}
 
-        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-            formatter.write_str("a string")
-        }
+impl<'de> serde::Deserialize<'de> for FastStr {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        fast_str(deserializer)
+    }
+}
 
-        fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
-        where
-            E: Error,
-        {
-            Ok(FastStr::new(v))
+impl serde::Serialize for LocalFastStr {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.as_str().serialize(serializer)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for LocalFastStr {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        fast_str(deserializer).map(LocalFastStr::from)
+    }
+}
+
+impl serde::Serialize for FastStrVec {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut seq = serializer.serialize_seq(Some(self.len()))?;
+        for i in 0..self.len() {
+            seq.serialize_element(&self[i])?;
         }
+        seq.end()
+    }
+}
 
-        fn visit_borrowed_str<E>(self, v: &'a str) -> Result<Self::Value, E>
-        where
-            E: Error,
-        {
-            Ok(FastStr::new(v))
+impl<'de> serde::Deserialize<'de> for FastStrVec {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct FastStrVecVisitor;
+
+        impl<'de> Visitor<'de> for FastStrVecVisitor {
+            type Value = FastStrVec;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a sequence of strings")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: SeqAccess<'de>,
+            {
+                let mut vec = FastStrVec::new();
+                while seq.next_element_seed(PushSeed(&mut vec))?.is_some() {}
+                Ok(vec)
+            }
         }
 
-        fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
-        where
-            E: Error,
+        deserializer.deserialize_seq(FastStrVecVisitor)
+    }
+}
+
+/// Deserializes a string straight into the buffer of a [`FastStrVec`], without creating an
+/// intermediate `FastStr`.
+struct PushSeed<'a>(&'a mut FastStrVec);
+
+impl<'de> DeserializeSeed<'de> for PushSeed<'_> {
+    type Value = ();
+
+    fn deserialize<D>(self, deserializer: D) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(self)
+    }
+}
+
+impl Visitor<'_> for PushSeed<'_> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a string")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<(), E>
+    where
+        E: Error,
+    {
+        self.0.push(v);
+        Ok(())
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<(), E>
+    where
+        E: Error,
+    {
+        #[cfg(feature = "serde-unsafe")]
         {
-            Ok(FastStr::from(v))
+            // Safety: the `serde-unsafe` feature trusts the input to be valid utf-8.
+            self.visit_str(unsafe { core::str::from_utf8_unchecked(v) })
         }
+        #[cfg(not(feature = "serde-unsafe"))]
+        match simdutf8::basic::from_utf8(v) {
+            Ok(s) => self.visit_str(s),
+            Err(_) => Err(Error::invalid_value(Unexpected::Bytes(v), &self)),
+        }
+    }
+}
 
-        fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
-        where
-            E: Error,
-        {
-            #[cfg(feature = "serde-unsafe")]
+impl<const MAX: usize> serde::Serialize for BoundedFastStr<MAX> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.as_str().serialize(serializer)
+    }
+}
+
+impl<'de, const MAX: usize> serde::Deserialize<'de> for BoundedFastStr<MAX> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        // Checks the length before anything is copied.
+        struct BoundedVisitor<const MAX: usize>;
+
+        impl<'a, const MAX: usize> Visitor<'a> for BoundedVisitor<MAX> {
+            type Value = BoundedFastStr<MAX>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                write!(formatter, "a string of at most {MAX} bytes")
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+            where
+                E: Error,
             {
-                Ok(unsafe { FastStr::new_u8_slice_unchecked(v) })
+                BoundedFastStr::new(v).map_err(|_| Error::invalid_length(v.len(), &self))
             }
-            #[cfg(not(feature = "serde-unsafe"))]
-            match FastStr::new_u8_slice(v) {
-                Ok(s) => Ok(s),
-                Err(_) => Err(Error::invalid_value(Unexpected::Bytes(v), &self)),
+
+            fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
+            where
+                E: Error,
+            {
+                let len = v.len();
+                BoundedFastStr::from_string(v).map_err(|_| Error::invalid_length(len, &self))
             }
-        }
 
-        fn visit_borrowed_bytes<E>(self, v: &'a [u8]) -> Result<Self::Value, E>
-        where
-            E: Error,
-        {
-            #[cfg(feature = "serde-unsafe")]
+            fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+            where
+                E: Error,
             {
-                Ok(unsafe { FastStr::new_u8_slice_unchecked(v) })
+                BoundedFastStr::<MAX>::check(v.len())
+                    .map_err(|_| Error::invalid_length(v.len(), &self))?;
+                FastStrVisitor.visit_bytes(v).map(BoundedFastStr)
             }
-            #[cfg(not(feature = "serde-unsafe"))]
-            match FastStr::new_u8_slice(v) {
-                Ok(s) => Ok(s),
-                Err(_) => Err(Error::invalid_value(Unexpected::Bytes(v), &self)),
+
+            fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<Self::Value, E>
+            where
+                E: Error,
+            {
+                BoundedFastStr::<MAX>::check(v.len())
+                    .map_err(|_| Error::invalid_length(v.len(), &self))?;
+                FastStrVisitor.visit_byte_buf(v).map(BoundedFastStr)
             }
         }
 
-        fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<Self::Value, E>
-        where
-            E: Error,
-        {
-            #[cfg(not(feature = "serde-unsafe"))]
-            simdutf8::basic::from_utf8(&v)
-                .map_err(|_| Error::invalid_value(Unexpected::Bytes(&v), &self))?;
-            // Safety: we have checked that v is valid utf-8
-            Ok(unsafe { FastStr::from_vec_u8_unchecked(v) })
-        }
+        deserializer.deserialize_string(BoundedVisitor::<MAX>)
     }
-
-    deserializer.deserialize_string(FastStrVisitor)
 }
 
-impl serde::Serialize for FastStr {
+impl<V> serde::Serialize for Validated<V> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
@@ -96,11 +285,102 @@ impl serde::Serialize for FastStr {
     }
 }
 
-impl<'de> serde::Deserialize<'de> for FastStr {
+impl<'de, V: Validator> serde::Deserialize<'de> for Validated<V> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: serde::Deserializer<'de>,
     {
-        fast_str(deserializer)
+        Self::new(fast_str(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl serde::Serialize for crate::SecretFastStr {
+    /// Write [`REDACTED`](crate::secret::REDACTED), use
+    /// [`serialize_exposed`](crate::secret::serialize_exposed) to write the value.
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        crate::secret::REDACTED.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<'de> serde::Deserialize<'de> for crate::SecretFastStr {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        fast_str(deserializer).map(Self::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_faststr_vec_round_trip() {
+        let long = "a string that is too long to be inlined";
+        let vec: FastStrVec = ["a", "", long, "with \"escapes\""].into_iter().collect();
+        let json = serde_json::to_string(&vec).unwrap();
+        assert_eq!(
+            json,
+            r#"["a","",""#.to_owned() + long + r#"","with \"escapes\""]"#
+        );
+        let back: FastStrVec = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, vec);
+        let back: FastStrVec = serde_json::from_reader(json.as_bytes()).unwrap();
+        assert_eq!(back, vec);
+        assert!(serde_json::from_str::<FastStrVec>("[1]").is_err());
+    }
+    #[test]
+    fn test_local_round_trip() {
+        let s = LocalFastStr::from_static_str("a string that is too long to be inlined");
+        let json = serde_json::to_string(&s).unwrap();
+        assert_eq!(serde_json::from_str::<LocalFastStr>(&json).unwrap(), s);
+        assert_eq!(
+            serde_json::from_str::<LocalFastStr>(r#""short""#).unwrap(),
+            "short"
+        );
+    }
+
+    #[test]
+    fn test_validated_round_trip() {
+        use crate::validate::{Identifier, NonEmpty};
+
+        crate::validated!(Name: (NonEmpty, Identifier));
+
+        let name = Name::new("user_service").unwrap();
+        let json = serde_json::to_string(&name).unwrap();
+        assert_eq!(json, r#""user_service""#);
+        assert_eq!(serde_json::from_str::<Name>(&json).unwrap(), name);
+        assert!(serde_json::from_str::<Name>(r#""user-service""#).is_err());
+    }
+
+    #[cfg(feature = "zeroize")]
+    #[test]
+    fn test_secret_redacted() {
+        use crate::{secret, SecretFastStr};
+
+        let token = SecretFastStr::new(FastStr::from_static_str("s3cr3t"));
+        assert_eq!(serde_json::to_string(&token).unwrap(), r#""[REDACTED]""#);
+
+        let mut json = Vec::new();
+        secret::serialize_exposed(&token, &mut serde_json::Serializer::new(&mut json)).unwrap();
+        assert_eq!(json, br#""s3cr3t""#);
+    }
+
+    #[test]
+    fn test_bounded_length() {
+        let at_limit: BoundedFastStr<5> = serde_json::from_str(r#""hello""#).unwrap();
+        assert_eq!(at_limit, "hello");
+        let escaped: BoundedFastStr<5> = serde_json::from_str(r#""hell\u006f""#).unwrap();
+        assert_eq!(escaped, "hello");
+
+        let err = serde_json::from_str::<BoundedFastStr<5>>(r#""hello!""#).unwrap_err();
+        assert!(err.to_string().contains("a string of at most 5 bytes"));
+        assert!(serde_json::from_reader::<_, BoundedFastStr<5>>(&br#""hello!""#[..]).is_err());
     }
 }
@@ -1,7 +1,7 @@
 use sqlx::{encode::IsNull, error::BoxDynError, Decode, Encode, Type};
 use sqlx_mysql::{MySql, MySqlTypeInfo, MySqlValueRef};
 
-use crate::FastStr;
+use crate::{BoundedFastStr, FastStr};
 
 impl Type<MySql> for FastStr {
     fn type_info() -> MySqlTypeInfo {
@@ -38,3 +38,29 @@ impl Encode<'_, MySql> for FastStr {
         <&str as Encode<MySql>>::size_hint(&self.as_str())
     }
 }
+
+impl<const MAX: usize> Type<MySql> for BoundedFastStr<MAX> {
+    fn type_info() -> MySqlTypeInfo {
+        <str as Type<MySql>>::type_info()
+    }
+
+    fn compatible(ty: &MySqlTypeInfo) -> bool {
+        <str as Type<MySql>>::compatible(ty)
+    }
+}
+
+impl<'r, const MAX: usize> Decode<'r, MySql> for BoundedFastStr<MAX> {
+    fn decode(value: MySqlValueRef<'r>) -> Result<Self, BoxDynError> {
+        let s = <FastStr as Decode<MySql>>::decode(value)?;
+        BoundedFastStr::try_from(s).map_err(|e| Box::new(e) as BoxDynError)
+    }
+}
+
+impl<const MAX: usize> Encode<'_, MySql> for BoundedFastStr<MAX> {
+    fn encode_by_ref(&self, buf: &mut Vec<u8>) -> Result<IsNull, BoxDynError> {
+        <&str as Encode<MySql>>::encode(self.as_str(), buf)
+    }
+    fn size_hint(&self) -> usize {
+        <&str as Encode<MySql>>::size_hint(&self.as_str())
+    }
+}
@@ -1,7 +1,7 @@
 use sqlx::{encode::IsNull, error::BoxDynError, Decode, Encode, Type};
 use sqlx_postgres::{PgTypeInfo, PgValueRef, Postgres};
 
-use crate::FastStr;
+use crate::{BoundedFastStr, FastStr};
 
 impl Type<Postgres> for FastStr {
     fn type_info() -> PgTypeInfo {
@@ -40,3 +40,32 @@ impl Encode<'_, Postgres> for FastStr {
         <&str as Encode<Postgres>>::size_hint(&self.as_str())
     }
 }
+
+impl<const MAX: usize> Type<Postgres> for BoundedFastStr<MAX> {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::with_name("varchar")
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        <str as Type<Postgres>>::compatible(ty)
+    }
+}
+
+impl<'r, const MAX: usize> Decode<'r, Postgres> for BoundedFastStr<MAX> {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        let s = <FastStr as Decode<Postgres>>::decode(value)?;
+        BoundedFastStr::try_from(s).map_err(|e| Box::new(e) as BoxDynError)
+    }
+}
+
+impl<const MAX: usize> Encode<'_, Postgres> for BoundedFastStr<MAX> {
+    fn encode_by_ref(
+        &self,
+        buf: &mut <Postgres as sqlx::Database>::ArgumentBuffer<'_>,
+    ) -> Result<IsNull, BoxDynError> {
+        <&str as Encode<Postgres>>::encode(self.as_str(), buf)
+    }
+    fn size_hint(&self) -> usize {
+        <&str as Encode<Postgres>>::size_hint(&self.as_str())
+    }
+}
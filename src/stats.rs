@@ -0,0 +1,162 @@
+//! Allocation and copy statistics, enabled by the `stats` feature.
+//!
+//! Every `FastStr` created is counted per representation, along with the bytes copied by
+//! operations that look cheap but have to copy for some representations. The counters are
+//! process-wide relaxed atomics, read them with [`snapshot`].
+//!
+//! `FastStr::empty` and `FastStr::from_static_str` are `const` and are not counted.
+
+#[cfg(feature = "stats")]
+use core::sync::atomic::{AtomicUsize, Ordering::Relaxed};
+
+use crate::ReprKind;
+
+/// The operations that may copy the bytes of a string behind the scenes.
+#[derive(Clone, Copy)]
+pub(crate) enum CopyPath {
+    /// `Repr::new` with a string too long to be inlined.
+    New,
+    /// `slice_ref` on an `Arc<str>` or `Arc<String>`.
+    SliceRef,
+    /// `into_bytes` on an inline string, an `Arc<str>` or a shared `Arc<String>`.
+    IntoBytes,
+    /// `deep_clone_bytes` and `compact_if`.
+    DeepClone,
+}
+
+#[cfg(feature = "stats")]
+#[allow(clippy::declare_interior_mutable_const)]
+const ZERO: AtomicUsize = AtomicUsize::new(0);
+
+#[cfg(feature = "stats")]
+static CREATED: [AtomicUsize; 7] = [ZERO; 7];
+#[cfg(feature = "stats")]
+static COPIED_BYTES: [AtomicUsize; 4] = [ZERO; 4];
+#[cfg(feature = "stats")]
+static INLINE_PROMOTIONS: AtomicUsize = ZERO;
+
+#[inline(always)]
+pub(crate) fn record_created(kind: ReprKind) {
+    #[cfg(feature = "stats")]
+    CREATED[kind as usize].fetch_add(1, Relaxed);
+    #[cfg(not(feature = "stats"))]
+    let _ = kind;
+}
+
+#[inline(always)]
+pub(crate) fn record_copy(path: CopyPath, len: usize) {
+    #[cfg(feature = "stats")]
+    COPIED_BYTES[path as usize].fetch_add(len, Relaxed);
+    #[cfg(feature = "strict-zero-copy")]
+    match path {
+        CopyPath::SliceRef => crate::strict::check_copy("slice_ref", len),
+        CopyPath::IntoBytes => crate::strict::check_copy("into_bytes", len),
+        // Copying is what these are asked to do.
+        CopyPath::New | CopyPath::DeepClone => {}
+    }
+    #[cfg(not(any(feature = "stats", feature = "strict-zero-copy")))]
+    let _ = (path, len);
+}
+
+/// Record that `len` bytes of a heap-allocated string have been copied into an inline
+/// `FastStr`.
+#[inline(always)]
+pub(crate) fn record_inline_promotion(len: usize) {
+    #[cfg(feature = "stats")]
+    INLINE_PROMOTIONS.fetch_add(1, Relaxed);
+    #[cfg(feature = "strict-zero-copy")]
+    crate::strict::check_copy("inlining", len);
+    #[cfg(not(feature = "strict-zero-copy"))]
+    let _ = len;
+}
+
+/// A point-in-time copy of the statistics, returned by [`snapshot`].
+#[cfg(feature = "stats")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Snapshot {
+    /// Number of empty strings created.
+    pub created_empty: usize,
+    /// Number of strings created as a view into a `Bytes`.
+    pub created_bytes: usize,
+    /// Number of strings created from an `Arc<str>`.
+    pub created_arc_str: usize,
+    /// Number of strings created from a shared `Arc<String>`.
+    pub created_arc_string: usize,
+    /// Number of static strings created by slicing a static string.
+    pub created_static: usize,
+    /// Number of inline strings created.
+    pub created_inline: usize,
+    /// Number of strings wrapped by
+    /// [`FastStr::cache_char_metadata`](crate::FastStr::cache_char_metadata).
+    pub created_cached: usize,
+    /// Bytes copied by creating a `FastStr` from a string slice too long to be inlined.
+    pub new_copied_bytes: usize,
+    /// Bytes copied by `slice_ref` on an `Arc<str>` or `Arc<String>`.
+    pub slice_ref_copied_bytes: usize,
+    /// Bytes copied by `into_bytes` on an inline string, an `Arc<str>` or a shared
+    /// `Arc<String>`.
+    pub into_bytes_copied_bytes: usize,
+    /// Bytes copied by `deep_clone_bytes` and `compact_if`.
+    pub deep_clone_copied_bytes: usize,
+    /// Number of heap-allocated strings copied into an inline `FastStr` because they were short
+    /// enough.
+    pub inline_promotions: usize,
+}
+
+/// Return the current value of every counter.
+#[cfg(feature = "stats")]
+pub fn snapshot() -> Snapshot {
+    let created = |kind: ReprKind| CREATED[kind as usize].load(Relaxed);
+    let copied = |path: CopyPath| COPIED_BYTES[path as usize].load(Relaxed);
+    Snapshot {
+        created_empty: created(ReprKind::Empty),
+        created_bytes: created(ReprKind::Bytes),
+        created_arc_str: created(ReprKind::ArcStr),
+        created_arc_string: created(ReprKind::ArcString),
+        created_static: created(ReprKind::Static),
+        created_inline: created(ReprKind::Inline),
+        created_cached: created(ReprKind::Cached),
+        new_copied_bytes: copied(CopyPath::New),
+        slice_ref_copied_bytes: copied(CopyPath::SliceRef),
+        into_bytes_copied_bytes: copied(CopyPath::IntoBytes),
+        deep_clone_copied_bytes: copied(CopyPath::DeepClone),
+        inline_promotions: INLINE_PROMOTIONS.load(Relaxed),
+    }
+}
+
+/// Reset every counter to zero.
+#[cfg(feature = "stats")]
+pub fn reset() {
+    CREATED
+        .iter()
+        .chain(&COPIED_BYTES)
+        .chain([&INLINE_PROMOTIONS])
+        .for_each(|counter| counter.store(0, Relaxed));
+}
+
+#[cfg(all(test, feature = "stats"))]
+mod tests {
+    use alloc::{string::String, sync::Arc};
+
+    use super::*;
+    use crate::FastStr;
+
+    #[test]
+    fn test_snapshot() {
+        let long = "a string that is too long to be inlined";
+        let before = snapshot();
+        let s = FastStr::new(long);
+        let _ = FastStr::from_string(String::from("short"));
+        let arc = FastStr::from_arc_str(Arc::from(long));
+        let _ = arc.slice_ref(&arc[1..]).into_bytes();
+        let after = snapshot();
+
+        // Tests run concurrently, so other tests may bump the counters too.
+        assert!(after.created_bytes >= before.created_bytes + 2);
+        assert!(after.created_arc_str > before.created_arc_str);
+        assert!(after.inline_promotions > before.inline_promotions);
+        assert!(after.new_copied_bytes >= before.new_copied_bytes + s.len());
+        assert!(after.slice_ref_copied_bytes >= before.slice_ref_copied_bytes + long.len() - 1);
+    }
+}
@@ -0,0 +1,80 @@
+//! Strict zero-copy mode, enabled by the `strict-zero-copy` feature.
+
+use std::cell::Cell;
+
+thread_local! {
+    static DENY_DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Run `f`, panicking if a `FastStr` operation expected to be cheap copies bytes on this thread
+/// in the meantime.
+///
+/// The operations checked are `slice_ref` on an `Arc<str>` or `Arc<String>`, `into_bytes` on a
+/// string that does not own a `Bytes`, and the constructors from `Bytes`, `String` and `Arc`
+/// that copy a short string into an inline `FastStr`. Operations that copy by design, such as
+/// [`FastStr::new`](crate::FastStr::new) or
+/// [`FastStr::deep_clone_bytes`](crate::FastStr::deep_clone_bytes), are allowed. Run with
+/// `RUST_BACKTRACE=1` to find where the copy came from.
+///
+/// Calls may be nested, and the check is only active on the calling thread.
+///
+/// ```should_panic
+/// use std::sync::Arc;
+///
+/// use faststr::FastStr;
+///
+/// let s = FastStr::from_arc_str(Arc::from("a string that is too long to be inlined"));
+/// faststr::deny_copies(|| s.slice_ref(&s[1..]));
+/// ```
+pub fn deny_copies<R>(f: impl FnOnce() -> R) -> R {
+    struct Guard;
+
+    impl Drop for Guard {
+        fn drop(&mut self) {
+            DENY_DEPTH.with(|depth| depth.set(depth.get() - 1));
+        }
+    }
+
+    DENY_DEPTH.with(|depth| depth.set(depth.get() + 1));
+    let _guard = Guard;
+    f()
+}
+
+#[inline]
+pub(crate) fn check_copy(op: &str, len: usize) {
+    if DENY_DEPTH.with(Cell::get) > 0 {
+        panic!("faststr: {op} copied {len} bytes inside `deny_copies`");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{panic, sync::Arc};
+
+    use bytes::Bytes;
+
+    use super::*;
+    use crate::FastStr;
+
+    #[test]
+    fn test_deny_copies() {
+        let long = "a string that is too long to be inlined";
+        let bytes = FastStr::from_bytes(Bytes::from_static(long.as_bytes())).unwrap();
+        let shared: Arc<String> = Arc::new(long.into());
+        let arc = FastStr::from_arc_string(shared.clone());
+
+        deny_copies(|| {
+            let _ = bytes.slice_ref(&bytes[1..]);
+            let _ = bytes.clone().into_bytes();
+            let _ = FastStr::new("copies by design");
+        });
+        assert!(panic::catch_unwind(|| deny_copies(|| arc.slice_ref(&arc[1..]))).is_err());
+        assert!(panic::catch_unwind(|| deny_copies(|| arc.clone().into_bytes())).is_err());
+        assert!(
+            panic::catch_unwind(|| deny_copies(|| FastStr::from_bytes(Bytes::from("short"))))
+                .is_err()
+        );
+        // The guard is released after a panic.
+        let _ = arc.slice_ref(&arc[1..]);
+    }
+}
@@ -0,0 +1,490 @@
+use alloc::string::String;
+use core::{fmt, marker::PhantomData, str::FromStr};
+
+use crate::FastStr;
+
+/// A check run on a string before it is wrapped in a [`Validated`].
+///
+/// Tuples of validators run each of them in order.
+pub trait Validator {
+    /// Return an error if `s` is not valid.
+    fn validate(s: &str) -> Result<(), ValidationError>;
+}
+
+/// The error returned when a string fails a [`Validator`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ValidationError {
+    reason: &'static str,
+}
+
+impl ValidationError {
+    /// Create a new error with a short description of the failed check.
+    #[inline]
+    pub const fn new(reason: &'static str) -> Self {
+        Self { reason }
+    }
+
+    /// Return the description of the failed check.
+    #[inline]
+    pub fn reason(&self) -> &'static str {
+        self.reason
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid string: {}", self.reason)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ValidationError {}
+
+/// A `FastStr` that has passed the validator `V`.
+///
+/// Use [`validated!`](crate::validated) to give it a name:
+///
+/// ```
+/// use faststr::validate::{Identifier, MaxLen, NonEmpty};
+///
+/// faststr::validated!(pub ServiceName: (NonEmpty, MaxLen<64>, Identifier));
+///
+/// let name: ServiceName = "user_service".parse().unwrap();
+/// assert_eq!(name, "user_service");
+/// assert!(ServiceName::new("user-service").is_err());
+/// ```
+pub struct Validated<V> {
+    inner: FastStr,
+    _validator: PhantomData<fn() -> V>,
+}
+
+impl<V: Validator> Validated<V> {
+    /// Validate `s` and wrap it.
+    #[inline]
+    pub fn new<S: Into<FastStr>>(s: S) -> Result<Self, ValidationError> {
+        let inner = s.into();
+        V::validate(&inner)?;
+        Ok(Self {
+            inner,
+            _validator: PhantomData,
+        })
+    }
+}
+
+impl<V> Validated<V> {
+    /// Wrap `s` without validating it.
+    ///
+    /// This is not `unsafe`, but code relying on the validator may misbehave.
+    #[inline]
+    pub fn new_unchecked(s: FastStr) -> Self {
+        Self {
+            inner: s,
+            _validator: PhantomData,
+        }
+    }
+}
+
+impl<V> Clone for Validated<V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self::new_unchecked(self.inner.clone())
+    }
+}
+
+impl_faststr_newtype!([V] Validated<V>, inner);
+
+impl<V: Validator> TryFrom<FastStr> for Validated<V> {
+    type Error = ValidationError;
+
+    #[inline]
+    fn try_from(s: FastStr) -> Result<Self, Self::Error> {
+        Self::new(s)
+    }
+}
+
+impl<V: Validator> TryFrom<String> for Validated<V> {
+    type Error = ValidationError;
+
+    #[inline]
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        Self::new(s)
+    }
+}
+
+impl<V: Validator> TryFrom<&'static str> for Validated<V> {
+    type Error = ValidationError;
+
+    #[inline]
+    fn try_from(s: &'static str) -> Result<Self, Self::Error> {
+        Self::new(s)
+    }
+}
+
+impl<V: Validator> FromStr for Validated<V> {
+    type Err = ValidationError;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        V::validate(s)?;
+        Ok(Self::new_unchecked(FastStr::new(s)))
+    }
+}
+
+/// Declare a named [`Validated`] type.
+///
+/// `validated!(pub Name: V)` declares `pub struct Name(faststr::Validated<V>);`, attributes such
+/// as doc comments are kept. `Name` has the constructors and conversions of `Validated<V>`,
+/// derefs to it, and with the `serde` feature of faststr it is (de)serialized as a string that
+/// is validated on the way in.
+#[macro_export]
+macro_rules! validated {
+    ($(#[$meta:meta])* $vis:vis $name:ident : $validator:ty) => {
+        $(#[$meta])*
+        #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        $vis struct $name($crate::Validated<$validator>);
+
+        #[allow(dead_code)]
+        impl $name {
+            /// Validate `s` and wrap it.
+            #[inline]
+            pub fn new<S: ::core::convert::Into<$crate::FastStr>>(
+                s: S,
+            ) -> ::core::result::Result<Self, $crate::ValidationError> {
+                $crate::Validated::new(s).map(Self)
+            }
+
+            /// Wrap `s` without validating it.
+            ///
+            /// This is not `unsafe`, but code relying on the validator may misbehave.
+            #[inline]
+            pub fn new_unchecked(s: $crate::FastStr) -> Self {
+                Self($crate::Validated::new_unchecked(s))
+            }
+
+            /// Consume and return the underlying `FastStr`.
+            #[inline]
+            pub fn into_inner(self) -> $crate::FastStr {
+                self.0.into_inner()
+            }
+        }
+
+        impl ::core::ops::Deref for $name {
+            type Target = $crate::Validated<$validator>;
+
+            #[inline]
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+
+        impl ::core::convert::AsRef<str> for $name {
+            #[inline]
+            fn as_ref(&self) -> &str {
+                self.0.as_str()
+            }
+        }
+
+        impl ::core::borrow::Borrow<str> for $name {
+            #[inline]
+            fn borrow(&self) -> &str {
+                self.0.as_str()
+            }
+        }
+
+        impl ::core::convert::From<$crate::Validated<$validator>> for $name {
+            #[inline]
+            fn from(s: $crate::Validated<$validator>) -> Self {
+                Self(s)
+            }
+        }
+
+        impl ::core::convert::From<$name> for $crate::Validated<$validator> {
+            #[inline]
+            fn from(s: $name) -> Self {
+                s.0
+            }
+        }
+
+        impl ::core::convert::From<$name> for $crate::FastStr {
+            #[inline]
+            fn from(s: $name) -> Self {
+                s.0.into_inner()
+            }
+        }
+
+        impl ::core::convert::TryFrom<$crate::FastStr> for $name {
+            type Error = $crate::ValidationError;
+
+            #[inline]
+            fn try_from(s: $crate::FastStr) -> ::core::result::Result<Self, Self::Error> {
+                Self::new(s)
+            }
+        }
+
+        impl ::core::convert::TryFrom<$crate::__private::String> for $name {
+            type Error = $crate::ValidationError;
+
+            #[inline]
+            fn try_from(s: $crate::__private::String) -> ::core::result::Result<Self, Self::Error> {
+                Self::new(s)
+            }
+        }
+
+        impl ::core::convert::TryFrom<&'static str> for $name {
+            type Error = $crate::ValidationError;
+
+            #[inline]
+            fn try_from(s: &'static str) -> ::core::result::Result<Self, Self::Error> {
+                Self::new($crate::FastStr::from_static_str(s))
+            }
+        }
+
+        impl ::core::str::FromStr for $name {
+            type Err = $crate::ValidationError;
+
+            #[inline]
+            fn from_str(s: &str) -> ::core::result::Result<Self, Self::Err> {
+                Self::new($crate::FastStr::new(s))
+            }
+        }
+
+        impl ::core::cmp::PartialEq<str> for $name {
+            #[inline]
+            fn eq(&self, other: &str) -> bool {
+                self.0 == *other
+            }
+        }
+
+        impl ::core::cmp::PartialEq<&str> for $name {
+            #[inline]
+            fn eq(&self, other: &&str) -> bool {
+                self.0 == *other
+            }
+        }
+
+        impl ::core::cmp::PartialEq<$crate::FastStr> for $name {
+            #[inline]
+            fn eq(&self, other: &$crate::FastStr) -> bool {
+                self.0 == *other
+            }
+        }
+
+        impl ::core::fmt::Debug for $name {
+            #[inline]
+            fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                ::core::fmt::Debug::fmt(&self.0, f)
+            }
+        }
+
+        impl ::core::fmt::Display for $name {
+            #[inline]
+            fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                ::core::fmt::Display::fmt(&self.0, f)
+            }
+        }
+
+        $crate::__validated_serde!($name);
+    };
+}
+
+/// Forward the serde impls of [`Validated`] to a type declared by [`validated!`].
+///
+/// This is chosen by the features of faststr, not of the crate calling `validated!`.
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __validated_serde {
+    ($name:ident) => {
+        impl $crate::__private::serde::Serialize for $name {
+            #[inline]
+            fn serialize<S>(&self, serializer: S) -> ::core::result::Result<S::Ok, S::Error>
+            where
+                S: $crate::__private::serde::Serializer,
+            {
+                $crate::__private::serde::Serialize::serialize(&self.0, serializer)
+            }
+        }
+
+        impl<'de> $crate::__private::serde::Deserialize<'de> for $name {
+            #[inline]
+            fn deserialize<D>(deserializer: D) -> ::core::result::Result<Self, D::Error>
+            where
+                D: $crate::__private::serde::Deserializer<'de>,
+            {
+                $crate::__private::serde::Deserialize::deserialize(deserializer).map(Self)
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "serde"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __validated_serde {
+    ($name:ident) => {};
+}
+
+/// The built-in [`Validator`]s.
+pub mod validate {
+    use core::marker::PhantomData;
+
+    use super::{ValidationError, Validator};
+
+    /// Rejects the empty string.
+    pub struct NonEmpty;
+
+    impl Validator for NonEmpty {
+        #[inline]
+        fn validate(s: &str) -> Result<(), ValidationError> {
+            if s.is_empty() {
+                return Err(ValidationError::new("string is empty"));
+            }
+            Ok(())
+        }
+    }
+
+    /// Rejects strings longer than `N` bytes.
+    pub struct MaxLen<const N: usize>;
+
+    impl<const N: usize> Validator for MaxLen<N> {
+        #[inline]
+        fn validate(s: &str) -> Result<(), ValidationError> {
+            if s.len() > N {
+                return Err(ValidationError::new("string is too long"));
+            }
+            Ok(())
+        }
+    }
+
+    /// A set of characters, used by [`Charset`].
+    pub trait CharClass {
+        /// Return `true` if `c` belongs to the set.
+        fn contains(c: char) -> bool;
+    }
+
+    /// Rejects strings containing a character outside of `C`.
+    pub struct Charset<C>(PhantomData<C>);
+
+    impl<C: CharClass> Validator for Charset<C> {
+        #[inline]
+        fn validate(s: &str) -> Result<(), ValidationError> {
+            if !s.chars().all(C::contains) {
+                return Err(ValidationError::new(
+                    "string contains a forbidden character",
+                ));
+            }
+            Ok(())
+        }
+    }
+
+    /// ASCII letters and digits.
+    pub struct AsciiAlphanumeric;
+
+    impl CharClass for AsciiAlphanumeric {
+        #[inline]
+        fn contains(c: char) -> bool {
+            c.is_ascii_alphanumeric()
+        }
+    }
+
+    /// Printable ASCII characters, without the space.
+    pub struct AsciiGraphic;
+
+    impl CharClass for AsciiGraphic {
+        #[inline]
+        fn contains(c: char) -> bool {
+            c.is_ascii_graphic()
+        }
+    }
+
+    /// Accepts ASCII identifiers: a letter or `_`, followed by letters, digits or `_`.
+    pub struct Identifier;
+
+    impl Validator for Identifier {
+        fn validate(s: &str) -> Result<(), ValidationError> {
+            let mut bytes = s.bytes();
+            match bytes.next() {
+                Some(b) if b.is_ascii_alphabetic() || b == b'_' => {}
+                _ => return Err(ValidationError::new("string is not an identifier")),
+            }
+            if !bytes.all(|b| b.is_ascii_alphanumeric() || b == b'_') {
+                return Err(ValidationError::new("string is not an identifier"));
+            }
+            Ok(())
+        }
+    }
+
+    /// Accepts host names as defined by RFC 1123, with an optional trailing dot.
+    pub struct Hostname;
+
+    impl Validator for Hostname {
+        fn validate(s: &str) -> Result<(), ValidationError> {
+            const INVALID: ValidationError = ValidationError::new("string is not a host name");
+            let s = s.strip_suffix('.').unwrap_or(s);
+            if s.is_empty() || s.len() > 253 {
+                return Err(INVALID);
+            }
+            for label in s.split('.') {
+                let bytes = label.as_bytes();
+                if bytes.is_empty()
+                    || bytes.len() > 63
+                    || bytes[0] == b'-'
+                    || bytes[bytes.len() - 1] == b'-'
+                    || !bytes
+                        .iter()
+                        .all(|b| b.is_ascii_alphanumeric() || *b == b'-')
+                {
+                    return Err(INVALID);
+                }
+            }
+            Ok(())
+        }
+    }
+
+    macro_rules! impl_validator_for_tuple {
+        ($($v:ident),+) => {
+            impl<$($v: Validator),+> Validator for ($($v,)+) {
+                #[inline]
+                fn validate(s: &str) -> Result<(), ValidationError> {
+                    $($v::validate(s)?;)+
+                    Ok(())
+                }
+            }
+        };
+    }
+
+    impl_validator_for_tuple!(A);
+    impl_validator_for_tuple!(A, B);
+    impl_validator_for_tuple!(A, B, C);
+    impl_validator_for_tuple!(A, B, C, D);
+    impl_validator_for_tuple!(A, B, C, D, E);
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{format, string::ToString};
+
+    use super::{validate::*, *};
+
+    validated!(Host: (MaxLen<64>, Hostname));
+    validated!(Token: (NonEmpty, Charset<AsciiAlphanumeric>));
+
+    #[test]
+    fn test_validated() {
+        assert!(Host::new("example.com.").is_ok());
+        assert!(Host::new("a-1.example").is_ok());
+        for bad in ["", ".", "-a.com", "a..com", "a_b.com", &"a".repeat(65)] {
+            assert!(Host::new(String::from(bad)).is_err(), "{bad}");
+        }
+        assert_eq!(Token::try_from("").unwrap_err().reason(), "string is empty");
+        assert!(Token::try_from("abc 123").is_err());
+        assert_eq!(FastStr::from(Token::try_from("abc123").unwrap()), "abc123");
+
+        let host: Host = "example.com".parse().unwrap();
+        assert_eq!(host.to_string(), "example.com");
+        assert_eq!(format!("{host:?}"), r#""example.com""#);
+        assert_eq!(host.len(), 11);
+        let inner: Validated<(MaxLen<64>, Hostname)> = host.clone().into();
+        assert_eq!(Host::from(inner), host);
+    }
+}
@@ -0,0 +1,434 @@
+use alloc::{boxed::Box, sync::Arc, vec, vec::Vec};
+use core::{fmt, iter, ops::Index, ptr, ptr::NonNull, slice};
+
+use bytes::Bytes;
+
+use crate::{BytesRef, FastStr, INLINE_CAP};
+
+/// A compact list of strings stored in one contiguous buffer.
+///
+/// Like the string layout of Apache Arrow, the content of every element is packed into a single
+/// buffer and each element only costs a 4-byte end offset. Elements are returned as `FastStr`
+/// values that share the buffer, so reading never copies the content of long strings.
+///
+/// When the buffer is full, its content is moved into a new buffer twice as large. Elements
+/// handed out before keep the old buffer alive.
+#[derive(Clone, Default)]
+pub struct FastStrVec {
+    buf: Option<Buffer>,
+    ends: Vec<u32>,
+}
+
+impl FastStrVec {
+    /// Create an empty `FastStrVec`.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            buf: None,
+            ends: Vec::new(),
+        }
+    }
+
+    /// Create an empty `FastStrVec` with room for `len` elements of `bytes` bytes in total.
+    pub fn with_capacity(len: usize, bytes: usize) -> Self {
+        Self {
+            buf: (bytes > 0).then(|| Buffer::new(bytes)),
+            ends: Vec::with_capacity(len),
+        }
+    }
+
+    /// Return the number of elements.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.ends.len()
+    }
+
+    /// Return `true` if there are no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.ends.is_empty()
+    }
+
+    /// Return the total length in bytes of all elements.
+    #[inline]
+    pub fn bytes_len(&self) -> usize {
+        self.ends.last().map_or(0, |&end| end as usize)
+    }
+
+    /// Append a string to the end of the list.
+    ///
+    /// This panics if the total length of all elements exceeds `u32::MAX` bytes.
+    pub fn push<S: AsRef<str>>(&mut self, s: S) {
+        let s = s.as_ref();
+        let end = self.bytes_len() + s.len();
+        assert!(
+            end <= u32::MAX as usize,
+            "[FastStr] FastStrVec buffer exceeds u32::MAX bytes"
+        );
+        if !s.is_empty() {
+            self.reserve_bytes(s.len());
+            // `reserve_bytes` has made room for `s`.
+            self.buf.as_mut().unwrap().append(s);
+        }
+        self.ends.push(end as u32);
+    }
+
+    /// Return the element at `index` as a `FastStr` sharing the buffer.
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<FastStr> {
+        let s = self.get_str(index)?;
+        match &self.buf {
+            Some(buf) => Some(buf.share(s)),
+            None => Some(FastStr::empty()),
+        }
+    }
+
+    /// Return the element at `index` as a string slice.
+    #[inline]
+    pub fn get_str(&self, index: usize) -> Option<&str> {
+        let end = *self.ends.get(index)? as usize;
+        let start = match index {
+            0 => 0,
+            _ => self.ends[index - 1] as usize,
+        };
+        Some(&self.buffer()[start..end])
+    }
+
+    /// Return an iterator over the elements as `FastStr` values sharing the buffer.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_> {
+        Iter {
+            vec: self,
+            front: 0,
+            back: self.len(),
+        }
+    }
+
+    /// Remove all elements.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.buf = None;
+        self.ends.clear();
+    }
+
+    /// Sort the elements, repacking them into a new buffer in sorted order.
+    pub fn sort(&mut self) {
+        let mut order: Vec<usize> = (0..self.len()).collect();
+        order.sort_by_key(|&i| &self[i]);
+        let mut sorted = Self::with_capacity(self.len(), self.bytes_len());
+        for i in order {
+            sorted.push(&self[i]);
+        }
+        *self = sorted;
+    }
+
+    #[inline]
+    fn buffer(&self) -> &str {
+        self.buf.as_ref().map_or("", Buffer::as_str)
+    }
+
+    fn reserve_bytes(&mut self, additional: usize) {
+        if self
+            .buf
+            .as_ref()
+            .is_some_and(|buf| buf.remaining() >= additional)
+        {
+            return;
+        }
+        let len = self.bytes_len();
+        let cap = (len + additional).max(len * 2).max(64);
+        let mut buf = Buffer::new(cap);
+        buf.append(self.buffer());
+        self.buf = Some(buf);
+    }
+}
+
+/// The buffer of a [`FastStrVec`].
+///
+/// Bytes are only ever written past `len`, and the elements are shared through a `Bytes` that
+/// never covers more than the bytes already written. Once written, bytes are never modified.
+struct Buffer {
+    chunk: Arc<RawChunk>,
+    /// The end of the bytes this buffer may write. A clone gets no room at all, so that only
+    /// one buffer ever writes into a chunk.
+    cap: usize,
+    len: usize,
+    /// A view over the first bytes of the chunk, covering every element that cannot be inlined.
+    sealed: Option<BytesRef>,
+}
+
+impl Buffer {
+    fn new(cap: usize) -> Self {
+        Self {
+            chunk: Arc::new(RawChunk::new(cap)),
+            cap,
+            len: 0,
+            sealed: None,
+        }
+    }
+
+    #[inline]
+    fn remaining(&self) -> usize {
+        self.cap - self.len
+    }
+
+    /// Append `s`, which must fit in the remaining room.
+    fn append(&mut self, s: &str) {
+        assert!(s.len() <= self.remaining());
+        // Safety: the destination is inside the room of this buffer, which no view covers and
+        // no other buffer writes to.
+        unsafe {
+            ptr::copy_nonoverlapping(s.as_ptr(), self.chunk.ptr.as_ptr().add(self.len), s.len());
+        }
+        self.len += s.len();
+        if s.len() > INLINE_CAP {
+            self.seal();
+        }
+    }
+
+    /// Share everything written so far through `sealed`.
+    fn seal(&mut self) {
+        let prefix = Prefix {
+            chunk: self.chunk.clone(),
+            len: self.len,
+        };
+        self.sealed = Some(Bytes::from_owner(prefix).into());
+    }
+
+    /// Return everything written so far.
+    #[inline]
+    fn as_str(&self) -> &str {
+        // Safety: the first `len` bytes have been written with string slices, and are never
+        // modified again.
+        unsafe {
+            core::str::from_utf8_unchecked(slice::from_raw_parts(self.chunk.ptr.as_ptr(), self.len))
+        }
+    }
+
+    /// Return a `FastStr` sharing `subset`, which must be an element taken from
+    /// [`Buffer::as_str`].
+    #[inline]
+    fn share(&self, subset: &str) -> FastStr {
+        match &self.sealed {
+            Some(sealed) if !FastStr::can_inline(subset) => {
+                // Safety: every element that cannot be inlined is covered by `sealed`.
+                unsafe { FastStr::from_bytes_ref_unchecked(sealed.slice_ref(subset.as_bytes())) }
+            }
+            _ => FastStr::new(subset),
+        }
+    }
+}
+
+impl Clone for Buffer {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            chunk: self.chunk.clone(),
+            cap: self.len,
+            len: self.len,
+            sealed: self.sealed.clone(),
+        }
+    }
+}
+
+/// A heap allocation written through a raw pointer, so that no reference to it ever covers the
+/// bytes that are still being written.
+pub(crate) struct RawChunk {
+    pub(crate) ptr: NonNull<u8>,
+    cap: usize,
+}
+
+// Safety: `RawChunk` owns its allocation. `Buffer` and `FastStrArena` only write bytes that no
+// view covers, from the single owner that has room for them.
+unsafe impl Send for RawChunk {}
+unsafe impl Sync for RawChunk {}
+
+impl RawChunk {
+    pub(crate) fn new(cap: usize) -> Self {
+        let ptr = Box::into_raw(vec![0u8; cap].into_boxed_slice()) as *mut u8;
+        Self {
+            // Safety: `Box::into_raw` never returns a null pointer.
+            ptr: unsafe { NonNull::new_unchecked(ptr) },
+            cap,
+        }
+    }
+}
+
+impl Drop for RawChunk {
+    fn drop(&mut self) {
+        // Safety: the allocation was created by `RawChunk::new` with this length.
+        drop(unsafe { Box::from_raw(ptr::slice_from_raw_parts_mut(self.ptr.as_ptr(), self.cap)) });
+    }
+}
+
+/// The owner of a `Bytes` over the bytes of a chunk written before it was created.
+struct Prefix {
+    chunk: Arc<RawChunk>,
+    len: usize,
+}
+
+impl AsRef<[u8]> for Prefix {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        // Safety: the first `len` bytes have been written and are never modified again.
+        unsafe { slice::from_raw_parts(self.chunk.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl Index<usize> for FastStrVec {
+    type Output = str;
+
+    #[inline]
+    fn index(&self, index: usize) -> &str {
+        match self.get_str(index) {
+            Some(s) => s,
+            None => panic!(
+                "index out of bounds: the len is {} but the index is {}",
+                self.len(),
+                index
+            ),
+        }
+    }
+}
+
+impl PartialEq for FastStrVec {
+    fn eq(&self, other: &Self) -> bool {
+        self.ends == other.ends && self.buffer() == other.buffer()
+    }
+}
+
+impl Eq for FastStrVec {}
+
+impl fmt::Debug for FastStrVec {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list()
+            .entries((0..self.len()).map(|i| &self[i]))
+            .finish()
+    }
+}
+
+impl<S: AsRef<str>> Extend<S> for FastStrVec {
+    fn extend<I: IntoIterator<Item = S>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.ends.reserve(iter.size_hint().0);
+        for s in iter {
+            self.push(s);
+        }
+    }
+}
+
+impl<S: AsRef<str>> iter::FromIterator<S> for FastStrVec {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = S>>(iter: I) -> Self {
+        let mut vec = Self::new();
+        vec.extend(iter);
+        vec
+    }
+}
+
+impl<'a> IntoIterator for &'a FastStrVec {
+    type Item = FastStr;
+    type IntoIter = Iter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Iter<'a> {
+        self.iter()
+    }
+}
+
+/// An iterator over the elements of a [`FastStrVec`].
+#[derive(Clone, Debug)]
+pub struct Iter<'a> {
+    vec: &'a FastStrVec,
+    front: usize,
+    back: usize,
+}
+
+impl Iterator for Iter<'_> {
+    type Item = FastStr;
+
+    #[inline]
+    fn next(&mut self) -> Option<FastStr> {
+        if self.front == self.back {
+            return None;
+        }
+        self.front += 1;
+        self.vec.get(self.front - 1)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for Iter<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<FastStr> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        self.vec.get(self.back)
+    }
+}
+
+impl ExactSizeIterator for Iter<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_and_get() {
+        let long = "a string that is too long to be inlined";
+        let mut vec: FastStrVec = ["b", "", long].into_iter().collect();
+        for _ in 0..10 {
+            vec.push(long);
+        }
+        assert_eq!(vec.len(), 13);
+        assert_eq!(&vec[0], "b");
+        assert_eq!(&vec[1], "");
+        assert_eq!(vec.get(2).unwrap(), long);
+        assert!(vec.get(13).is_none());
+
+        let first = vec.get(2).unwrap();
+        vec.push(long.repeat(10));
+        assert_eq!(first, long);
+        assert_eq!(vec.iter().next_back().unwrap(), long.repeat(10));
+        assert_eq!(vec.iter().rev().count(), 14);
+    }
+
+    #[test]
+    fn test_shares_buffer() {
+        let long = "a string that is too long to be inlined";
+        let mut vec = FastStrVec::with_capacity(3, 1024);
+        vec.push(long);
+        vec.push("short");
+        let first = vec.get(0).unwrap();
+        let cloned = vec.clone();
+        vec.push(long);
+        assert!(first.shares_allocation_with(&vec.get(2).unwrap()));
+        assert!(vec.get(1).unwrap().is_inline());
+        assert_eq!(first.clone().into_bytes(), long.as_bytes());
+
+        // The clone has no room, so it never writes into the tail of the original buffer.
+        let mut cloned = cloned;
+        cloned.push("another string that is too long to be inlined");
+        assert!(!first.shares_allocation_with(&cloned.get(2).unwrap()));
+        assert_eq!(vec.get(2).unwrap(), long);
+        assert_eq!(&cloned[0], long);
+    }
+
+    static_assertions::assert_impl_all!(FastStrVec: Send, Sync);
+
+    #[test]
+    fn test_sort() {
+        let mut vec: FastStrVec = ["c", "a", "", "b"].into_iter().collect();
+        let cloned = vec.clone();
+        vec.sort();
+        assert_eq!(vec, ["", "a", "b", "c"].into_iter().collect());
+        assert_eq!(&cloned[0], "c");
+    }
+}
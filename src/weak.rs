@@ -0,0 +1,145 @@
+use alloc::{
+    string::String,
+    sync::{Arc, Weak},
+};
+use core::fmt;
+
+use bytes::Bytes;
+
+use crate::{BytesRef, CachedRepr, FastStr, ISize, Repr, INLINE_CAP};
+
+/// A weak reference to a `FastStr`, created by [`FastStr::downgrade`].
+///
+/// A `WeakFastStr` does not keep the backing buffer of the string alive. Strings that are empty,
+/// static or inline own no buffer, so they always upgrade successfully.
+#[derive(Clone, Default)]
+pub struct WeakFastStr(WeakRepr);
+
+// Safety: `ptr` points into the `Bytes` held by `data`, which is `Send` and `Sync`.
+unsafe impl Send for WeakFastStr {}
+unsafe impl Sync for WeakFastStr {}
+
+#[derive(Clone, Default)]
+enum WeakRepr {
+    #[default]
+    Empty,
+    Bytes {
+        ptr: *const u8,
+        len: usize,
+        data: Weak<Bytes>,
+    },
+    ArcStr(Weak<str>),
+    ArcString(Weak<String>),
+    StaticStr(&'static str),
+    Inline {
+        len: ISize,
+        buf: [u8; INLINE_CAP],
+    },
+    Cached(Weak<CachedRepr>),
+}
+
+impl FastStr {
+    /// Create a [`WeakFastStr`] pointing to the same string.
+    #[inline]
+    pub fn downgrade(&self) -> WeakFastStr {
+        WeakFastStr(match &self.0 {
+            Repr::Empty => WeakRepr::Empty,
+            Repr::Bytes(b) => WeakRepr::Bytes {
+                ptr: b.ptr,
+                len: b.len,
+                data: Arc::downgrade(&b.data),
+            },
+            Repr::ArcStr(s) => WeakRepr::ArcStr(Arc::downgrade(s)),
+            Repr::ArcString(s) => WeakRepr::ArcString(Arc::downgrade(s)),
+            Repr::StaticStr(s) => WeakRepr::StaticStr(s),
+            Repr::Inline { len, buf } => WeakRepr::Inline {
+                len: *len,
+                buf: *buf,
+            },
+            Repr::Cached(c) => WeakRepr::Cached(Arc::downgrade(c)),
+        })
+    }
+}
+
+impl WeakFastStr {
+    /// Create a `WeakFastStr` to the empty string.
+    #[inline]
+    pub const fn new() -> Self {
+        Self(WeakRepr::Empty)
+    }
+
+    /// Return the string if its backing buffer is still alive.
+    #[inline]
+    pub fn upgrade(&self) -> Option<FastStr> {
+        let repr = match &self.0 {
+            WeakRepr::Empty => Repr::Empty,
+            WeakRepr::Bytes { ptr, len, data } => Repr::Bytes(BytesRef {
+                ptr: *ptr,
+                len: *len,
+                data: data.upgrade()?,
+            }),
+            WeakRepr::ArcStr(s) => Repr::ArcStr(s.upgrade()?),
+            WeakRepr::ArcString(s) => Repr::ArcString(s.upgrade()?),
+            WeakRepr::StaticStr(s) => Repr::StaticStr(s),
+            WeakRepr::Inline { len, buf } => Repr::Inline {
+                len: *len,
+                buf: *buf,
+            },
+            WeakRepr::Cached(c) => Repr::Cached(c.upgrade()?),
+        };
+        Some(FastStr(repr))
+    }
+
+    /// Return the number of strong references to the backing buffer, or `None` if the string
+    /// owns no buffer.
+    #[inline]
+    pub fn strong_count(&self) -> Option<usize> {
+        match &self.0 {
+            WeakRepr::Bytes { data, .. } => Some(data.strong_count()),
+            WeakRepr::ArcStr(s) => Some(s.strong_count()),
+            WeakRepr::ArcString(s) => Some(s.strong_count()),
+            // A dead cache no longer holds a reference to the buffer.
+            WeakRepr::Cached(c) => c.upgrade().map_or(Some(0), |c| c.repr.ref_count()),
+            WeakRepr::Empty | WeakRepr::StaticStr(_) | WeakRepr::Inline { .. } => None,
+        }
+    }
+}
+
+impl fmt::Debug for WeakFastStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("(WeakFastStr)")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_upgrade() {
+        let long = "a string that is too long to be inlined";
+        for s in [
+            FastStr::new(long),
+            FastStr::from_arc_str(Arc::from(long)),
+            FastStr::from_arc_string(Arc::new(String::from(long))),
+        ] {
+            let slice = s.slice_ref(&s[1..]);
+            drop(s);
+            let weak = slice.downgrade();
+            assert_eq!(weak.strong_count(), Some(1));
+            assert_eq!(weak.upgrade().unwrap(), long[1..]);
+            drop(slice);
+            assert!(weak.upgrade().is_none());
+        }
+
+        for s in [
+            FastStr::empty(),
+            FastStr::from_static_str(long),
+            FastStr::new("inline"),
+        ] {
+            let weak = s.downgrade();
+            drop(s);
+            assert!(weak.upgrade().is_some());
+        }
+    }
+}
@@ -1,12 +1,16 @@
 import gdb
 
 VARIANT_NAMES = ['Empty', 'Bytes', 'ArcStr',
-                 'ArcString', 'StaticStr', 'Inline']
+                 'ArcString', 'StaticStr', 'Inline', 'Cached']
 
 
 class FastStrPrettyPrinter:
     def __init__(self, valobj: gdb.Value):
-        repr_obj = valobj['__0']
+        self._is_error = False
+        self._variant_name, self._display_string = self._extract_repr(
+            valobj['__0'])
+
+    def _extract_repr(self, repr_obj: gdb.Value) -> tuple[str, str]:
         inner = repr_obj[repr_obj.type.fields()[0]]
         fields = inner.type.fields()
         discr = int(inner[fields[0]]) + 1
@@ -14,25 +18,23 @@ class FastStrPrettyPrinter:
         assert field_name == VARIANT_NAMES[discr-1]
         variant = inner[fields[discr]]
 
-        self._variant_name = field_name
-        self._display_string = ''
-        self._is_error = False
-
         if discr == 1:
-            pass
+            return field_name, ''
         elif discr == 2:
-            self._display_string = self._extract_bytes(variant)
+            return field_name, self._extract_bytes(variant)
         elif discr == 3:
-            self._display_string = self._extract_arc_str(variant)
+            return field_name, self._extract_arc_str(variant)
         elif discr == 4:
-            self._display_string = self._extract_arc_string(variant)
+            return field_name, self._extract_arc_string(variant)
         elif discr == 5:
-            self._display_string = self._extract_static_str(variant)
+            return field_name, self._extract_static_str(variant)
         elif discr == 6:
-            self._display_string = self._extract_inline(variant)
+            return field_name, self._extract_inline(variant)
+        elif discr == 7:
+            return field_name, self._extract_cached(variant)
         else:
-            self._display_string = '<Invalid FastStr>'
             self._is_error = True
+            return field_name, '<Invalid FastStr>'
 
     def _extract_bytes(self, variant: gdb.Value) -> str:
         try:
@@ -98,6 +100,17 @@ class FastStrPrettyPrinter:
             self._is_error = True
             return f'<Error reading Inline: {e}>'
 
+    def _extract_cached(self, variant: gdb.Value) -> str:
+        try:
+            arc_obj = variant['__0']
+            non_null_ptr = arc_obj['ptr']
+            arc_inner_ptr = non_null_ptr['pointer']
+            arc_inner = arc_inner_ptr.dereference()
+            return self._extract_repr(arc_inner['data']['repr'])[1]
+        except Exception as e:
+            self._is_error = True
+            return f'<Error reading Cached: {e}>'
+
     def to_string(self) -> str:
         return self._display_string if self._is_error else f'FastStr::{self._variant_name}("{self._display_string}")'
 
@@ -2,7 +2,7 @@ import lldb
 import re
 
 VARIANT_NAMES = ['Empty', 'Bytes', 'ArcStr',
-                 'ArcString', 'StaticStr', 'Inline']
+                 'ArcString', 'StaticStr', 'Inline', 'Cached']
 
 
 class FastStrSyntheticProvider:
@@ -12,7 +12,11 @@ class FastStrSyntheticProvider:
         self.update()
 
     def update(self):
-        repr = self.valobj.GetChildAtIndex(0)
+        self._is_error = False
+        self._variant_name, self._display_string = self._extract_repr(
+            self.valobj.GetChildAtIndex(0))
+
+    def _extract_repr(self, repr: lldb.SBValue) -> tuple[str, str]:
         variants = repr.GetChildMemberWithName('$variants$')
         discr = variants.GetChildAtIndex(0).GetChildMemberWithName(
             '$discr$').GetValueAsUnsigned()
@@ -20,25 +24,23 @@ class FastStrSyntheticProvider:
         variant_name = self._get_variant_name(variant)
         assert variant_name == VARIANT_NAMES[discr]
 
-        self._variant_name = variant_name
-        self._display_string = ''
-        self._is_error = False
-
         if variant_name == 'Empty':
-            self._display_string = ''
+            return variant_name, ''
         elif variant_name == 'Bytes':
-            self._display_string = self._extract_bytes(variant)
+            return variant_name, self._extract_bytes(variant)
         elif variant_name == 'ArcStr':
-            self._display_string = self._extract_arc_str(variant)
+            return variant_name, self._extract_arc_str(variant)
         elif variant_name == 'ArcString':
-            self._display_string = self._extract_arc_string(variant)
+            return variant_name, self._extract_arc_string(variant)
         elif variant_name == 'StaticStr':
-            self._display_string = self._extract_static_str(variant)
+            return variant_name, self._extract_static_str(variant)
         elif variant_name == 'Inline':
-            self._display_string = self._extract_inline(variant)
+            return variant_name, self._extract_inline(variant)
+        elif variant_name == 'Cached':
+            return variant_name, self._extract_cached(variant)
         else:
-            self._display_string = '<Invalid FastStr>'
             self._is_error = True
+            return variant_name, '<Invalid FastStr>'
 
     def _get_variant_name(self, variant: lldb.SBValue):
         full_name = variant.GetType().GetName()
@@ -131,6 +133,18 @@ class FastStrSyntheticProvider:
             self._is_error = True
             return f'<Error reading Inline: {error.GetCString()}>'
 
+    def _extract_cached(self, variant: lldb.SBValue) -> str:
+        # Arc<CachedRepr>
+        arc = variant.GetChildMemberWithName(
+            'value').GetChildMemberWithName('__0')
+        ptr = arc.GetChildMemberWithName('ptr')
+        # ArcInner
+        arc_inner = ptr.GetChildMemberWithName('pointer')
+        # the wrapped Repr
+        repr = arc_inner.GetChildMemberWithName(
+            'data').GetChildMemberWithName('repr')
+        return self._extract_repr(repr)[1]
+
     def to_string(self) -> str:
         return self._display_string if self._is_error else f'FastStr::{self._variant_name}("{self._display_string}")'
 